@@ -359,6 +359,11 @@ impl CatalogState {
             CatalogItem::Source(source) => match &source.connector {
                 SourceConnector::External { connector, .. } => match &connector {
                     ExternalSourceConnector::PubNub(_) => Volatile,
+                    ExternalSourceConnector::Prometheus(_) => Volatile,
+                    ExternalSourceConnector::Otlp(_) => Volatile,
+                    ExternalSourceConnector::Tempo(_) => Volatile,
+                    ExternalSourceConnector::Syslog(_) => Volatile,
+                    ExternalSourceConnector::Fluent(_) => Volatile,
                     ExternalSourceConnector::Kinesis(_) => Volatile,
                     _ => Unknown,
                 },
@@ -957,7 +957,21 @@ pub const MZ_KAFKA_SOURCE_STATISTICS: BuiltinLog = BuiltinLog {
     id: GlobalId::System(3040),
 };
 
-// Next id BuiltinLog: 3042
+pub const MZ_SOURCE_ERRORS: BuiltinLog = BuiltinLog {
+    name: "mz_source_errors",
+    schema: MZ_CATALOG_SCHEMA,
+    variant: LogVariant::Materialized(MaterializedLog::SourceErrors),
+    id: GlobalId::System(3042),
+};
+
+pub const MZ_SOURCE_INGESTION: BuiltinLog = BuiltinLog {
+    name: "mz_source_ingestion",
+    schema: MZ_CATALOG_SCHEMA,
+    variant: LogVariant::Materialized(MaterializedLog::SourceIngestion),
+    id: GlobalId::System(3044),
+};
+
+// Next id BuiltinLog: 3046
 
 lazy_static! {
     pub static ref MZ_VIEW_KEYS: BuiltinTable = BuiltinTable {
@@ -2170,6 +2184,8 @@ lazy_static! {
             Builtin::Log(&MZ_SCHEDULING_ELAPSED_INTERNAL),
             Builtin::Log(&MZ_SCHEDULING_HISTOGRAM_INTERNAL),
             Builtin::Log(&MZ_SCHEDULING_PARKS_INTERNAL),
+            Builtin::Log(&MZ_SOURCE_ERRORS),
+            Builtin::Log(&MZ_SOURCE_INGESTION),
             Builtin::Log(&MZ_SOURCE_INFO),
             Builtin::Log(&MZ_WORKER_MATERIALIZATION_FRONTIERS),
             Builtin::Table(&MZ_VIEW_KEYS),
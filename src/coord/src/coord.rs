@@ -127,12 +127,12 @@ use mz_sql::catalog::{CatalogError, CatalogTypeDetails, SessionCatalog as _};
 use mz_sql::names::{DatabaseSpecifier, FullName};
 use mz_sql::plan::{
     AlterIndexEnablePlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
-    AlterItemRenamePlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan, CreateSchemaPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan,
-    CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan,
-    ExplainPlan, FetchPlan, HirRelationExpr, IndexOption, IndexOptionName, InsertPlan,
-    MutationKind, Params, PeekPlan, PeekWhen, Plan, RaisePlan, ReadThenWritePlan, SendDiffsPlan,
-    SetVariablePlan, ShowVariablePlan, TailFrom, TailPlan,
+    AlterItemRenamePlan, AlterSourcePlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan,
+    CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan,
+    DropSchemaPlan, ExecutePlan, ExplainPlan, FetchPlan, HirRelationExpr, IndexOption,
+    IndexOptionName, InsertPlan, MutationKind, Params, PeekPlan, PeekWhen, Plan, RaisePlan,
+    ReadThenWritePlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan, TailFrom, TailPlan,
 };
 use mz_sql::plan::{OptimizerConfig, StatementDesc, View};
 use mz_transform::Optimizer;
@@ -1126,6 +1126,7 @@ impl Coordinator {
                                 | Statement::Discard(_)
                                 | Statement::Execute(_)
                                 | Statement::Explain(_)
+                                | Statement::ExplainSource(_)
                                 | Statement::Fetch(_)
                                 | Statement::Prepare(_)
                                 | Statement::Rollback(_)
@@ -1164,6 +1165,7 @@ impl Coordinator {
                                 // Statements below must by run singly (in Started).
                                 Statement::AlterIndex(_)
                                 | Statement::AlterObjectRename(_)
+                                | Statement::AlterSource(_)
                                 | Statement::CreateDatabase(_)
                                 | Statement::CreateIndex(_)
                                 | Statement::CreateRole(_)
@@ -1710,6 +1712,9 @@ impl Coordinator {
             Plan::AlterIndexEnable(plan) => {
                 tx.send(self.sequence_alter_index_enable(plan).await, session);
             }
+            Plan::AlterSource(plan) => {
+                tx.send(self.sequence_alter_source(plan).await, session);
+            }
             Plan::DiscardTemp => {
                 self.drop_temp_items(session.conn_id()).await;
                 tx.send(Ok(ExecuteResponse::DiscardedTemp), session);
@@ -3937,6 +3942,17 @@ impl Coordinator {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Index))
     }
 
+    async fn sequence_alter_source(
+        &mut self,
+        plan: AlterSourcePlan,
+    ) -> Result<ExecuteResponse, CoordError> {
+        self.dataflow_client
+            .storage()
+            .pause_sources(vec![(plan.id, plan.paused)])
+            .await?;
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
+    }
+
     /// Perform a catalog transaction.
     ///
     /// This is a simplified form of `catalog_transact_dataflow` for when no dataflow
@@ -13,6 +13,7 @@ use std::num::TryFromIntError;
 
 use dec::TryFromDecimalError;
 
+use mz_dataflow_types::client::controller::StorageError;
 use mz_dataflow_types::sources::{ExternalSourceConnector, SourceConnector};
 use mz_expr::{EvalError, UnmaterializableFunc};
 use mz_ore::stack::RecursionLimitError;
@@ -152,6 +153,10 @@ impl CoordError {
                 let source_name = match source_type {
                     RematerializedSourceType::Postgres => "Postgres",
                     RematerializedSourceType::S3 => "S3 with SQS notification ",
+                    RematerializedSourceType::Prometheus => "Prometheus remote write",
+                    RematerializedSourceType::Otlp => "OTLP",
+                    RematerializedSourceType::Syslog => "Syslog",
+                    RematerializedSourceType::Fluent => "Fluent",
                     RematerializedSourceType::PersistedSource => "Persisted",
                 };
                 Some(format!(
@@ -192,6 +197,10 @@ impl CoordError {
                 let doc_page = match source_type {
                     RematerializedSourceType::Postgres => "postgres",
                     RematerializedSourceType::S3 => "text-s3",
+                    RematerializedSourceType::Prometheus => "prometheus",
+                    RematerializedSourceType::Otlp => "otlp",
+                    RematerializedSourceType::Syslog => "syslog",
+                    RematerializedSourceType::Fluent => "fluent",
                     RematerializedSourceType::PersistedSource => {
                         // TODO: Make this more helpful once we have documentation for persisted
                         // sources.
@@ -339,6 +348,12 @@ impl From<TryFromDecimalError> for CoordError {
     }
 }
 
+impl From<StorageError> for CoordError {
+    fn from(e: StorageError) -> CoordError {
+        CoordError::Unstructured(e.into())
+    }
+}
+
 impl From<catalog::Error> for CoordError {
     fn from(e: catalog::Error) -> CoordError {
         CoordError::Catalog(e)
@@ -388,6 +403,10 @@ impl Error for CoordError {}
 pub enum RematerializedSourceType {
     Postgres,
     S3,
+    Prometheus,
+    Otlp,
+    Syslog,
+    Fluent,
     PersistedSource,
 }
 
@@ -406,6 +425,10 @@ impl RematerializedSourceType {
             SourceConnector::External { connector, .. } => match connector {
                 ExternalSourceConnector::S3(_) => RematerializedSourceType::S3,
                 ExternalSourceConnector::Postgres(_) => RematerializedSourceType::Postgres,
+                ExternalSourceConnector::Prometheus(_) => RematerializedSourceType::Prometheus,
+                ExternalSourceConnector::Otlp(_) => RematerializedSourceType::Otlp,
+                ExternalSourceConnector::Syslog(_) => RematerializedSourceType::Syslog,
+                ExternalSourceConnector::Fluent(_) => RematerializedSourceType::Fluent,
                 _ => unreachable!(),
             },
             _ => unreachable!(),
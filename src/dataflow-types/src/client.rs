@@ -189,6 +189,11 @@ pub enum StorageCommand<T = mz_repr::Timestamp> {
         /// The timestamp to advance to.
         advance_to: T,
     },
+    /// Pause or resume the ingestion of sources.
+    ///
+    /// Each entry names a source and whether it is to be paused. Paused sources stop reading or
+    /// accepting data until they are resumed, including instances that are rendered later.
+    PauseSources(Vec<(GlobalId, bool)>),
 }
 
 impl StorageCommandKind {
@@ -202,6 +207,7 @@ impl StorageCommandKind {
             StorageCommandKind::CreateSources => "create_sources",
             StorageCommandKind::DurabilityFrontierUpdates => "durability_frontier_updates",
             StorageCommandKind::Insert => "insert",
+            StorageCommandKind::PauseSources => "pause_sources",
             StorageCommandKind::RenderSources => "render_sources",
         }
     }
@@ -34,6 +34,7 @@ use crate::logging::LoggingConfig;
 
 pub use storage::StorageController;
 pub use storage::StorageControllerState;
+pub use storage::StorageError;
 mod storage;
 pub use compute::ComputeController;
 mod compute;
@@ -18,6 +18,8 @@
 //! is dropped with either `drop_sources()` or by allowing compaction to the empty frontier.
 
 use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
 
 use differential_dataflow::lattice::Lattice;
 use timely::progress::frontier::MutableAntichain;
@@ -55,6 +57,22 @@ pub enum StorageError {
     ClientError(anyhow::Error),
 }
 
+impl Error for StorageError {}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SourceIdReused(id) => write!(
+                f,
+                "source identifier was re-created after having been dropped: {}",
+                id
+            ),
+            Self::IdentifierMissing(id) => write!(f, "source identifier is not present: {}", id),
+            Self::ClientError(e) => write!(f, "{:#}", e),
+        }
+    }
+}
+
 impl From<anyhow::Error> for StorageError {
     fn from(error: anyhow::Error) -> Self {
         Self::ClientError(error)
@@ -169,6 +187,18 @@ impl<'a, C: Client<T>, T: Timestamp + Lattice> StorageController<'a, C, T> {
         Ok(())
     }
 
+    /// Pauses or resumes the ingestion of sources.
+    pub async fn pause_sources(
+        &mut self,
+        updates: Vec<(GlobalId, bool)>,
+    ) -> Result<(), StorageError> {
+        self.validate_ids(updates.iter().map(|(id, _)| *id))?;
+        self.client
+            .send(Command::Storage(StorageCommand::PauseSources(updates)))
+            .await
+            .map_err(StorageError::from)
+    }
+
     pub async fn advance_all_table_timestamps(
         &mut self,
         advance_to: T,
@@ -65,6 +65,8 @@ pub enum MaterializedLog {
     KafkaSourceStatistics,
     PeekCurrent,
     PeekDuration,
+    SourceErrors,
+    SourceIngestion,
     SourceInfo,
 }
 
@@ -172,6 +174,22 @@ impl LogVariant {
                 .with_column("timestamp", ScalarType::Int64.nullable(false))
                 .with_key(vec![0, 1, 2, 3]),
 
+            LogVariant::Materialized(MaterializedLog::SourceErrors) => RelationDesc::empty()
+                .with_column("source_id", ScalarType::String.nullable(false))
+                .with_column("dataflow_id", ScalarType::Int64.nullable(false))
+                .with_column("worker", ScalarType::Int64.nullable(false))
+                .with_column("timestamp", ScalarType::Int64.nullable(false))
+                .with_column("category", ScalarType::String.nullable(false))
+                .with_column("message", ScalarType::String.nullable(false)),
+
+            LogVariant::Materialized(MaterializedLog::SourceIngestion) => RelationDesc::empty()
+                .with_column("source_id", ScalarType::String.nullable(false))
+                .with_column("dataflow_id", ScalarType::Int64.nullable(false))
+                .with_column("worker", ScalarType::Int64.nullable(false))
+                .with_column("bytes", ScalarType::Int64.nullable(false))
+                .with_column("rows", ScalarType::Int64.nullable(false))
+                .with_key(vec![0, 1, 2]),
+
             LogVariant::Materialized(MaterializedLog::DataflowDependency) => RelationDesc::empty()
                 .with_column("dataflow", ScalarType::String.nullable(false))
                 .with_column("source", ScalarType::String.nullable(false))
@@ -252,6 +270,14 @@ impl LogVariant {
                 vec![(0, 1)],
             )],
             LogVariant::Materialized(MaterializedLog::PeekCurrent) => vec![],
+            LogVariant::Materialized(MaterializedLog::SourceErrors) => vec![(
+                LogVariant::Materialized(MaterializedLog::SourceInfo),
+                vec![(0, 1), (1, 2)],
+            )],
+            LogVariant::Materialized(MaterializedLog::SourceIngestion) => vec![(
+                LogVariant::Materialized(MaterializedLog::SourceInfo),
+                vec![(0, 1), (1, 2)],
+            )],
             LogVariant::Materialized(MaterializedLog::SourceInfo) => vec![],
             LogVariant::Materialized(MaterializedLog::PeekDuration) => vec![],
         }
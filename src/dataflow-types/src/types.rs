@@ -354,6 +354,7 @@ impl SourceInstanceDesc {
 pub mod sources {
 
     use std::collections::{BTreeMap, HashMap};
+    use std::net::SocketAddr;
     use std::ops::Add;
     use std::path::PathBuf;
     use std::time::Duration;
@@ -363,6 +364,7 @@ pub mod sources {
     use globset::Glob;
     use http::Uri;
     use serde::{Deserialize, Serialize};
+    use url::Url;
     use uuid::Uuid;
 
     use crate::gen::postgres_source::PostgresSourceDetails;
@@ -402,6 +404,16 @@ pub mod sources {
             Postgres,
             Bytes,
             Text,
+            Logfmt,
+            Json(JsonEncoding),
+            Syslog,
+            Cef,
+            AccessLog(AccessLogEncoding),
+            Msgpack,
+            Cbor,
+            Prometheus,
+            Otlp(OtlpSignal),
+            Fluent,
         }
 
         impl SourceDataEncoding {
@@ -513,6 +525,58 @@ pub mod sources {
                     },
                     DataEncoding::Text => RelationDesc::empty()
                         .with_column("text", ScalarType::String.nullable(false)),
+                    DataEncoding::Logfmt | DataEncoding::Msgpack | DataEncoding::Cbor => {
+                        RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
+                    }
+                    DataEncoding::Json(JsonEncoding { invalid }) => RelationDesc::empty()
+                        .with_column(
+                            "data",
+                            ScalarType::Jsonb.nullable(*invalid == InvalidJsonPolicy::Null),
+                        ),
+                    DataEncoding::Syslog => RelationDesc::empty()
+                        .with_column("priority", ScalarType::Int32.nullable(false))
+                        .with_column("facility", ScalarType::Int32.nullable(false))
+                        .with_column("severity", ScalarType::Int32.nullable(false))
+                        .with_column("timestamp", ScalarType::String.nullable(true))
+                        .with_column("hostname", ScalarType::String.nullable(true))
+                        .with_column("app_name", ScalarType::String.nullable(true))
+                        .with_column("procid", ScalarType::String.nullable(true))
+                        .with_column("msgid", ScalarType::String.nullable(true))
+                        .with_column("structured_data", ScalarType::Jsonb.nullable(true))
+                        .with_column("message", ScalarType::String.nullable(true)),
+                    DataEncoding::Cef => RelationDesc::empty()
+                        .with_column("version", ScalarType::Int32.nullable(false))
+                        .with_column("device_vendor", ScalarType::String.nullable(false))
+                        .with_column("device_product", ScalarType::String.nullable(false))
+                        .with_column("device_version", ScalarType::String.nullable(false))
+                        .with_column("signature_id", ScalarType::String.nullable(false))
+                        .with_column("name", ScalarType::String.nullable(false))
+                        .with_column("severity", ScalarType::String.nullable(false))
+                        .with_column(
+                            "extensions",
+                            ScalarType::Map {
+                                value_type: Box::new(ScalarType::String),
+                                custom_oid: None,
+                            }
+                            .nullable(false),
+                        ),
+                    DataEncoding::AccessLog(format) => {
+                        let desc = RelationDesc::empty()
+                            .with_column("remote_addr", ScalarType::String.nullable(false))
+                            .with_column("remote_user", ScalarType::String.nullable(true))
+                            .with_column("request_time", ScalarType::TimestampTz.nullable(false))
+                            .with_column("method", ScalarType::String.nullable(true))
+                            .with_column("path", ScalarType::String.nullable(true))
+                            .with_column("protocol", ScalarType::String.nullable(true))
+                            .with_column("status", ScalarType::Int32.nullable(false))
+                            .with_column("bytes", ScalarType::Int64.nullable(true));
+                        match format {
+                            AccessLogEncoding::Common => desc,
+                            AccessLogEncoding::Combined => desc
+                                .with_column("referer", ScalarType::String.nullable(true))
+                                .with_column("user_agent", ScalarType::String.nullable(true)),
+                        }
+                    }
                     DataEncoding::Postgres => RelationDesc::empty()
                         .with_column("oid", ScalarType::Int32.nullable(false))
                         .with_column(
@@ -523,6 +587,73 @@ pub mod sources {
                             }
                             .nullable(false),
                         ),
+                    DataEncoding::Prometheus => RelationDesc::empty()
+                        .with_column("metric", ScalarType::String.nullable(false))
+                        .with_column("labels", ScalarType::Jsonb.nullable(false))
+                        .with_column("timestamp", ScalarType::TimestampTz.nullable(false))
+                        .with_column("value", ScalarType::Float64.nullable(false)),
+                    DataEncoding::Fluent => RelationDesc::empty()
+                        .with_column("tag", ScalarType::String.nullable(false))
+                        .with_column("timestamp", ScalarType::TimestampTz.nullable(false))
+                        .with_column("record", ScalarType::Jsonb.nullable(false)),
+                    DataEncoding::Otlp(OtlpSignal::Logs) => RelationDesc::empty()
+                        .with_column("timestamp", ScalarType::TimestampTz.nullable(true))
+                        .with_column("observed_timestamp", ScalarType::TimestampTz.nullable(true))
+                        .with_column("severity_number", ScalarType::Int32.nullable(true))
+                        .with_column("severity_text", ScalarType::String.nullable(true))
+                        .with_column("body", ScalarType::Jsonb.nullable(true))
+                        .with_column("attributes", ScalarType::Jsonb.nullable(false))
+                        .with_column("trace_id", ScalarType::String.nullable(true))
+                        .with_column("span_id", ScalarType::String.nullable(true))
+                        .with_column("resource_attributes", ScalarType::Jsonb.nullable(false))
+                        .with_column("scope_name", ScalarType::String.nullable(true))
+                        .with_column("scope_version", ScalarType::String.nullable(true)),
+                    DataEncoding::Otlp(OtlpSignal::Traces) => RelationDesc::empty()
+                        .with_column("trace_id", ScalarType::String.nullable(false))
+                        .with_column("span_id", ScalarType::String.nullable(false))
+                        .with_column("parent_span_id", ScalarType::String.nullable(true))
+                        .with_column("name", ScalarType::String.nullable(false))
+                        .with_column("kind", ScalarType::String.nullable(true))
+                        .with_column("start_timestamp", ScalarType::TimestampTz.nullable(true))
+                        .with_column("end_timestamp", ScalarType::TimestampTz.nullable(true))
+                        .with_column("attributes", ScalarType::Jsonb.nullable(false))
+                        .with_column("status_code", ScalarType::String.nullable(true))
+                        .with_column("status_message", ScalarType::String.nullable(true))
+                        .with_column("resource_attributes", ScalarType::Jsonb.nullable(false))
+                        .with_column("scope_name", ScalarType::String.nullable(true))
+                        .with_column("scope_version", ScalarType::String.nullable(true)),
+                    DataEncoding::Otlp(OtlpSignal::Metrics) => RelationDesc::empty()
+                        .with_column("metric", ScalarType::String.nullable(false))
+                        .with_column("description", ScalarType::String.nullable(true))
+                        .with_column("unit", ScalarType::String.nullable(true))
+                        .with_column("type", ScalarType::String.nullable(false))
+                        .with_column("aggregation_temporality", ScalarType::String.nullable(true))
+                        .with_column("is_monotonic", ScalarType::Bool.nullable(true))
+                        .with_column("start_timestamp", ScalarType::TimestampTz.nullable(true))
+                        .with_column("timestamp", ScalarType::TimestampTz.nullable(true))
+                        .with_column("attributes", ScalarType::Jsonb.nullable(false))
+                        .with_column("value", ScalarType::Float64.nullable(true))
+                        .with_column("count", ScalarType::Int64.nullable(true))
+                        .with_column("sum", ScalarType::Float64.nullable(true))
+                        .with_column(
+                            "bucket_counts",
+                            ScalarType::List {
+                                element_type: Box::new(ScalarType::Int64),
+                                custom_oid: None,
+                            }
+                            .nullable(true),
+                        )
+                        .with_column(
+                            "explicit_bounds",
+                            ScalarType::List {
+                                element_type: Box::new(ScalarType::Float64),
+                                custom_oid: None,
+                            }
+                            .nullable(true),
+                        )
+                        .with_column("resource_attributes", ScalarType::Jsonb.nullable(false))
+                        .with_column("scope_name", ScalarType::String.nullable(true))
+                        .with_column("scope_version", ScalarType::String.nullable(true)),
                 })
             }
 
@@ -535,7 +666,17 @@ pub mod sources {
                     DataEncoding::Regex { .. } => "Regex",
                     DataEncoding::Csv(_) => "Csv",
                     DataEncoding::Text => "Text",
+                    DataEncoding::Logfmt => "Logfmt",
+                    DataEncoding::Json(_) => "Json",
+                    DataEncoding::Syslog => "Syslog",
+                    DataEncoding::Cef => "Cef",
+                    DataEncoding::AccessLog(_) => "AccessLog",
+                    DataEncoding::Msgpack => "Msgpack",
+                    DataEncoding::Cbor => "Cbor",
                     DataEncoding::Postgres => "Postgres",
+                    DataEncoding::Prometheus => "Prometheus",
+                    DataEncoding::Otlp(_) => "Otlp",
+                    DataEncoding::Fluent => "Fluent",
                 }
             }
         }
@@ -600,6 +741,54 @@ pub mod sources {
         pub struct RegexEncoding {
             pub regex: mz_repr::adt::regex::Regex,
         }
+
+        /// Arguments necessary to define how to decode from JSON format
+        #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+        pub struct JsonEncoding {
+            pub invalid: InvalidJsonPolicy,
+        }
+
+        /// Determines what happens to records that are not valid JSON
+        #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+        pub enum InvalidJsonPolicy {
+            /// Produce a decoding error.
+            Error,
+            /// Produce a row with a `NULL` value.
+            Null,
+            /// Produce a row with the record's text as a JSON string.
+            Text,
+        }
+
+        /// The flavor of web server access log to decode
+        #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+        pub enum AccessLogEncoding {
+            /// The Common Log Format.
+            Common,
+            /// The Combined Log Format, which adds the referer and user agent.
+            Combined,
+        }
+
+        /// The kind of telemetry that an OTLP source receives
+        #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+        pub enum OtlpSignal {
+            /// Log records.
+            Logs,
+            /// Spans.
+            Traces,
+            /// Metric data points.
+            Metrics,
+        }
+
+        impl OtlpSignal {
+            /// Returns the name of the signal, as used in OTLP/HTTP paths.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    OtlpSignal::Logs => "logs",
+                    OtlpSignal::Traces => "traces",
+                    OtlpSignal::Metrics => "metrics",
+                }
+            }
+        }
     }
 
     pub mod persistence {
@@ -1145,6 +1334,9 @@ pub mod sources {
             metadata_columns: Vec<IncludedColumnSource>,
             ts_frequency: Duration,
             timeline: Timeline,
+            /// How long each row lives after it is ingested. Once a row is
+            /// older than this, it is retracted.
+            retention: Option<Duration>,
         },
 
         /// A local "source" is either fed by a local input handle, or by reading from a
@@ -1224,6 +1416,11 @@ pub mod sources {
         S3(S3SourceConnector),
         Postgres(PostgresSourceConnector),
         PubNub(PubNubSourceConnector),
+        Prometheus(PrometheusSourceConnector),
+        Otlp(OtlpSourceConnector),
+        Tempo(TempoSourceConnector),
+        Syslog(SyslogSourceConnector),
+        Fluent(FluentSourceConnector),
     }
 
     impl ExternalSourceConnector {
@@ -1293,6 +1490,11 @@ pub mod sources {
                 }
                 Self::Postgres(_) => vec![],
                 Self::PubNub(_) => vec![],
+                Self::Prometheus(_) => vec![],
+                Self::Otlp(_) => vec![],
+                Self::Tempo(_) => vec![],
+                Self::Syslog(_) => vec![],
+                Self::Fluent(_) => vec![],
             }
         }
 
@@ -1306,6 +1508,11 @@ pub mod sources {
                 ExternalSourceConnector::S3(_) => Some("mz_record"),
                 ExternalSourceConnector::Postgres(_) => None,
                 ExternalSourceConnector::PubNub(_) => None,
+                ExternalSourceConnector::Prometheus(_) => None,
+                ExternalSourceConnector::Otlp(_) => None,
+                ExternalSourceConnector::Tempo(_) => None,
+                ExternalSourceConnector::Syslog(_) => None,
+                ExternalSourceConnector::Fluent(_) => None,
             }
         }
 
@@ -1349,9 +1556,13 @@ pub mod sources {
                         Vec::new()
                     }
                 }
-                ExternalSourceConnector::Postgres(_) | ExternalSourceConnector::PubNub(_) => {
-                    Vec::new()
-                }
+                ExternalSourceConnector::Postgres(_)
+                | ExternalSourceConnector::PubNub(_)
+                | ExternalSourceConnector::Prometheus(_)
+                | ExternalSourceConnector::Otlp(_)
+                | ExternalSourceConnector::Tempo(_)
+                | ExternalSourceConnector::Syslog(_)
+                | ExternalSourceConnector::Fluent(_) => Vec::new(),
             }
         }
 
@@ -1365,6 +1576,11 @@ pub mod sources {
                 ExternalSourceConnector::S3(_) => "s3",
                 ExternalSourceConnector::Postgres(_) => "postgres",
                 ExternalSourceConnector::PubNub(_) => "pubnub",
+                ExternalSourceConnector::Prometheus(_) => "prometheus",
+                ExternalSourceConnector::Otlp(_) => "otlp",
+                ExternalSourceConnector::Tempo(_) => "tempo",
+                ExternalSourceConnector::Syslog(_) => "syslog",
+                ExternalSourceConnector::Fluent(_) => "fluent",
             }
         }
 
@@ -1384,6 +1600,11 @@ pub mod sources {
                 ExternalSourceConnector::S3(_) => None,
                 ExternalSourceConnector::Postgres(_) => None,
                 ExternalSourceConnector::PubNub(_) => None,
+                ExternalSourceConnector::Prometheus(_) => None,
+                ExternalSourceConnector::Otlp(_) => None,
+                ExternalSourceConnector::Tempo(_) => None,
+                ExternalSourceConnector::Syslog(_) => None,
+                ExternalSourceConnector::Fluent(_) => None,
             }
         }
 
@@ -1391,12 +1612,18 @@ pub mod sources {
             match self {
                 ExternalSourceConnector::S3(c) => c.requires_single_materialization(),
                 ExternalSourceConnector::Postgres(_) => true,
+                ExternalSourceConnector::Prometheus(c) => c.requires_single_materialization(),
+                // Only one materialization can listen on the address.
+                ExternalSourceConnector::Otlp(_)
+                | ExternalSourceConnector::Syslog(_)
+                | ExternalSourceConnector::Fluent(_) => true,
 
                 ExternalSourceConnector::Kafka(_)
                 | ExternalSourceConnector::Kinesis(_)
                 | ExternalSourceConnector::File(_)
                 | ExternalSourceConnector::AvroOcf(_)
-                | ExternalSourceConnector::PubNub(_) => false,
+                | ExternalSourceConnector::PubNub(_)
+                | ExternalSourceConnector::Tempo(_) => false,
             }
         }
     }
@@ -1428,6 +1655,64 @@ pub mod sources {
         pub channel: String,
     }
 
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub enum PrometheusSourceConnector {
+        /// Accept the samples that Prometheus servers push with the remote
+        /// write protocol on `addr`.
+        RemoteWrite { addr: SocketAddr },
+        /// Scrape the metrics endpoint at `url` every `interval`.
+        Scrape { url: Url, interval: Duration },
+    }
+
+    impl PrometheusSourceConnector {
+        fn requires_single_materialization(&self) -> bool {
+            match self {
+                // Only one materialization can listen on the address.
+                PrometheusSourceConnector::RemoteWrite { .. } => true,
+                PrometheusSourceConnector::Scrape { .. } => false,
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct OtlpSourceConnector {
+        /// The address to accept OTLP/HTTP export requests on.
+        pub addr: SocketAddr,
+        pub signal: encoding::OtlpSignal,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct TempoSourceConnector {
+        /// The base URL of the Tempo API.
+        pub url: Url,
+        /// The tags that traces must match, in logfmt.
+        pub tags: String,
+        /// How often to search for new traces.
+        pub interval: Duration,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct SyslogSourceConnector {
+        /// The address to accept syslog messages on, over both UDP and TCP.
+        pub addr: SocketAddr,
+        /// If set, TCP connections must use TLS.
+        pub tls: Option<SyslogTlsConfig>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct SyslogTlsConfig {
+        /// The path to the PEM-encoded certificate chain.
+        pub cert: PathBuf,
+        /// The path to the PEM-encoded private key.
+        pub key: PathBuf,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct FluentSourceConnector {
+        /// The address to accept forward protocol connections on.
+        pub addr: SocketAddr,
+    }
+
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub struct S3SourceConnector {
         pub key_sources: Vec<S3KeySource>,
@@ -8,7 +8,19 @@
 // by the Apache License, Version 2.0.
 
 fn main() {
-    prost_build::Config::new()
-        .compile_protos(&["source/source.proto"], &["src"])
+    tonic_build::configure()
+        .build_client(false)
+        .compile_with_config(
+            prost_build::Config::new(),
+            &[
+                "source/source.proto",
+                "source/prometheus.proto",
+                "source/otlp.proto",
+                "source/otlp_logs_service.proto",
+                "source/otlp_trace_service.proto",
+                "source/otlp_metrics_service.proto",
+            ],
+            &["src"],
+        )
         .unwrap();
 }
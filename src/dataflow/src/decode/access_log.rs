@@ -0,0 +1,254 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of web server access logs in the Common Log Format and the
+//! Combined Log Format used by Apache and Nginx, e.g.
+//!
+//! ```text
+//! 127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /index.html HTTP/1.0" 200 2326 "http://example.com/" "Mozilla/4.08"
+//! ```
+//!
+//! The common format ends after the response size; the combined format adds
+//! the referer and the user agent. Any fields after those are ignored, which
+//! covers the many formats that extend the combined one.
+
+use std::borrow::Cow;
+
+use chrono::{DateTime, Utc};
+
+use mz_dataflow_types::sources::encoding::AccessLogEncoding;
+use mz_dataflow_types::DecodeError;
+use mz_repr::{Datum, Row};
+
+#[derive(Debug)]
+pub struct AccessLogDecoderState {
+    format: AccessLogEncoding,
+    row_buf: Row,
+}
+
+impl AccessLogDecoderState {
+    pub fn new(format: AccessLogEncoding) -> Self {
+        AccessLogDecoderState {
+            format,
+            row_buf: Row::default(),
+        }
+    }
+
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeError> {
+        let s = std::str::from_utf8(bytes)
+            .map_err(|_| DecodeError::Text("Failed to decode UTF-8".to_string()))?;
+        let entry = parse_access_log(s, self.format).map_err(DecodeError::Text)?;
+
+        let mut packer = self.row_buf.packer();
+        packer.push(Datum::String(entry.remote_addr));
+        packer.push(Datum::from(entry.remote_user));
+        packer.push(Datum::TimestampTz(entry.request_time));
+        packer.push(Datum::from(entry.method.as_deref()));
+        packer.push(Datum::from(entry.path.as_deref()));
+        packer.push(Datum::from(entry.protocol.as_deref()));
+        packer.push(Datum::Int32(entry.status));
+        packer.push(Datum::from(entry.bytes));
+        if self.format == AccessLogEncoding::Combined {
+            packer.push(Datum::from(entry.referer.as_deref()));
+            packer.push(Datum::from(entry.user_agent.as_deref()));
+        }
+        Ok(Some(self.row_buf.clone()))
+    }
+}
+
+/// An access log entry. Fields logged as `-` are `None`.
+#[derive(Debug, PartialEq)]
+struct AccessLogEntry<'a> {
+    remote_addr: &'a str,
+    remote_user: Option<&'a str>,
+    request_time: DateTime<Utc>,
+    method: Option<Cow<'a, str>>,
+    path: Option<Cow<'a, str>>,
+    protocol: Option<Cow<'a, str>>,
+    status: i32,
+    bytes: Option<i64>,
+    referer: Option<Cow<'a, str>>,
+    user_agent: Option<Cow<'a, str>>,
+}
+
+fn parse_access_log(line: &str, format: AccessLogEncoding) -> Result<AccessLogEntry, String> {
+    fn dash<T: AsRef<str>>(field: T) -> Option<T> {
+        if field.as_ref() == "-" {
+            None
+        } else {
+            Some(field)
+        }
+    }
+
+    let mut fields = Fields(line);
+    let remote_addr = fields.word().ok_or("missing remote address")?;
+    let _ident = fields.word().ok_or("missing identity")?;
+    let remote_user = dash(fields.word().ok_or("missing remote user")?);
+
+    let request_time = fields.delimited('[', ']').ok_or("missing request time")?;
+    let request_time = DateTime::parse_from_str(&request_time, "%d/%b/%Y:%H:%M:%S %z")
+        .map_err(|e| format!("invalid request time {}: {}", request_time, e))?
+        .with_timezone(&Utc);
+
+    // Malformed requests, e.g. from port scanners, are logged verbatim, so
+    // only split the request line if it has the expected shape.
+    let request = fields.delimited('"', '"').ok_or("missing request line")?;
+    let parts: Vec<Cow<str>> = match request {
+        Cow::Borrowed(request) => request.split(' ').map(Cow::Borrowed).collect(),
+        Cow::Owned(request) => request.split(' ').map(|p| p.to_owned().into()).collect(),
+    };
+    let mut parts = parts.into_iter();
+    let (method, path, protocol) = match parts.len() {
+        3 => (parts.next(), parts.next(), parts.next()),
+        2 => (parts.next(), parts.next(), None),
+        _ => (None, None, None),
+    };
+
+    let status = fields.word().ok_or("missing status")?;
+    let status = status
+        .parse()
+        .map_err(|_| format!("invalid status: {}", status))?;
+    let bytes = match dash(fields.word().ok_or("missing response size")?) {
+        None => None,
+        Some(bytes) => Some(
+            bytes
+                .parse()
+                .map_err(|_| format!("invalid response size: {}", bytes))?,
+        ),
+    };
+
+    let (referer, user_agent) = match format {
+        AccessLogEncoding::Common => (None, None),
+        AccessLogEncoding::Combined => (
+            dash(fields.delimited('"', '"').ok_or("missing referer")?),
+            dash(fields.delimited('"', '"').ok_or("missing user agent")?),
+        ),
+    };
+
+    Ok(AccessLogEntry {
+        remote_addr,
+        remote_user,
+        request_time,
+        method,
+        path,
+        protocol,
+        status,
+        bytes,
+        referer,
+        user_agent,
+    })
+}
+
+/// The not yet parsed remainder of an access log line.
+struct Fields<'a>(&'a str);
+
+impl<'a> Fields<'a> {
+    /// Returns the next space-separated field.
+    fn word(&mut self) -> Option<&'a str> {
+        let rest = self.0.trim_start_matches(' ');
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest.find(' ').unwrap_or(rest.len());
+        self.0 = &rest[end..];
+        Some(&rest[..end])
+    }
+
+    /// Returns the contents of the next field enclosed in `open` and `close`.
+    /// Within the field, `\` escapes the next character.
+    fn delimited(&mut self, open: char, close: char) -> Option<Cow<'a, str>> {
+        let rest = self.0.trim_start_matches(' ').strip_prefix(open)?;
+        let mut escaped: Option<String> = None;
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            if c == close {
+                self.0 = &rest[i + close.len_utf8()..];
+                return Some(match escaped {
+                    Some(s) => Cow::Owned(s),
+                    None => Cow::Borrowed(&rest[..i]),
+                });
+            } else if c == '\\' {
+                let s = escaped.get_or_insert_with(|| rest[..i].to_string());
+                s.extend(chars.next().map(|(_, c)| c));
+            } else if let Some(s) = escaped.as_mut() {
+                s.push(c);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use mz_dataflow_types::sources::encoding::AccessLogEncoding;
+
+    use super::{parse_access_log, AccessLogEntry};
+
+    #[test]
+    fn test_parse_access_log() {
+        let line = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08 \"quoted\"" 0.012"#;
+        let expected = AccessLogEntry {
+            remote_addr: "127.0.0.1",
+            remote_user: Some("frank"),
+            request_time: Utc.ymd(2000, 10, 10).and_hms(20, 55, 36),
+            method: Some("GET".into()),
+            path: Some("/apache_pb.gif".into()),
+            protocol: Some("HTTP/1.0".into()),
+            status: 200,
+            bytes: Some(2326),
+            referer: Some("http://www.example.com/start.html".into()),
+            user_agent: Some(r#"Mozilla/4.08 "quoted""#.into()),
+        };
+        assert_eq!(
+            parse_access_log(line, AccessLogEncoding::Combined),
+            Ok(expected)
+        );
+
+        let expected = AccessLogEntry {
+            remote_addr: "::1",
+            remote_user: None,
+            request_time: Utc.ymd(2021, 1, 2).and_hms(3, 4, 5),
+            method: None,
+            path: None,
+            protocol: None,
+            status: 400,
+            bytes: None,
+            referer: None,
+            user_agent: None,
+        };
+        assert_eq!(
+            parse_access_log(
+                r#"::1 - - [02/Jan/2021:03:04:05 +0000] "-" 400 -"#,
+                AccessLogEncoding::Common
+            ),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_parse_access_log_errors() {
+        let combined = AccessLogEncoding::Combined;
+        assert!(parse_access_log("", combined).is_err());
+        assert!(
+            parse_access_log("1.2.3.4 - - [yesterday] \"GET / HTTP/1.1\" 200 1", combined).is_err()
+        );
+        assert!(parse_access_log(
+            "1.2.3.4 - - [02/Jan/2021:03:04:05 +0000] \"GET / HTTP/1.1\" ok 1",
+            combined
+        )
+        .is_err());
+        assert!(parse_access_log(
+            "1.2.3.4 - - [02/Jan/2021:03:04:05 +0000] \"GET / HTTP/1.1\" 200 1",
+            combined
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,402 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of binary JSON-like formats, [MessagePack] and [CBOR], into
+//! `jsonb`.
+//!
+//! Both formats are richer than JSON. Map keys that are integers are converted
+//! to strings, byte strings become strings in the `bytea` hex format (e.g.
+//! `\x0102`), and CBOR tags are dropped. Values that have no JSON equivalent,
+//! like non-finite floats or integers that don't fit in 64 bits, produce a
+//! decoding error.
+//!
+//! [MessagePack]: https://github.com/msgpack/msgpack/blob/master/spec.md
+//! [CBOR]: https://datatracker.ietf.org/doc/html/rfc8949
+
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use mz_dataflow_types::DecodeError;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::Row;
+use serde_json::{Map, Number, Value};
+
+/// The maximum nesting depth of arrays and maps in a MessagePack value.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Default)]
+pub struct MsgpackDecoderState {
+    row_buf: Row,
+}
+
+impl MsgpackDecoderState {
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeError> {
+        let value = decode_msgpack(bytes)
+            .map_err(|e| DecodeError::Text(format!("Failed to decode MessagePack: {}", e)))?;
+        pack(&mut self.row_buf, value)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CborDecoderState {
+    row_buf: Row,
+}
+
+impl CborDecoderState {
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeError> {
+        let value = decode_cbor(bytes)
+            .map_err(|e| DecodeError::Text(format!("Failed to decode CBOR: {}", e)))?;
+        pack(&mut self.row_buf, value)
+    }
+}
+
+fn pack(row_buf: &mut Row, value: Value) -> Result<Option<Row>, DecodeError> {
+    JsonbPacker::new(&mut row_buf.packer())
+        .pack_serde_json(value)
+        .map_err(|e| DecodeError::Text(format!("Failed to pack jsonb: {}", e)))?;
+    Ok(Some(row_buf.clone()))
+}
+
+fn decode_msgpack(bytes: &[u8]) -> Result<Value, String> {
+    let mut reader = MsgpackReader::new(bytes);
+    let value = reader.read_json()?;
+    if !reader.is_empty() {
+        return Err(format!(
+            "{} trailing bytes after value",
+            bytes.len() - reader.pos
+        ));
+    }
+    Ok(value)
+}
+
+/// A reader of consecutive MessagePack values.
+///
+/// Besides converting values to JSON, it can read the structure of protocols
+/// that are built on MessagePack, like Fluentd's forward protocol, whose
+/// binary and extension values don't have a meaningful JSON equivalent.
+pub(crate) struct MsgpackReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> MsgpackReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        MsgpackReader { bytes, pos: 0 }
+    }
+
+    /// Reports whether all values have been read.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    /// Returns the marker byte of the next value without reading it.
+    pub(crate) fn peek_marker(&self) -> Result<u8, String> {
+        self.bytes
+            .get(self.pos)
+            .copied()
+            .ok_or_else(|| "unexpected end of input".into())
+    }
+
+    /// Reads the next value, which must be an array, up to its elements and
+    /// returns its length.
+    pub(crate) fn read_array_len(&mut self) -> Result<usize, String> {
+        match self.take_array::<1>()?[0] {
+            marker @ 0x90..=0x9f => Ok(usize::from(marker & 0x0f)),
+            marker @ (0xdc | 0xdd) => self.read_len(2 << (marker - 0xdc)),
+            marker => Err(format!("expected array, found marker byte {:#04x}", marker)),
+        }
+    }
+
+    /// Reads the next value, which must be a binary or string value, and
+    /// returns its bytes.
+    pub(crate) fn read_raw_bytes(&mut self) -> Result<&'a [u8], String> {
+        let len = match self.take_array::<1>()?[0] {
+            marker @ 0xa0..=0xbf => usize::from(marker & 0x1f),
+            marker @ 0xc4..=0xc6 => self.read_len(1 << (marker - 0xc4))?,
+            marker @ 0xd9..=0xdb => self.read_len(1 << (marker - 0xd9))?,
+            marker => {
+                return Err(format!(
+                    "expected binary or string, found marker byte {:#04x}",
+                    marker
+                ))
+            }
+        };
+        self.take(len)
+    }
+
+    /// Reads the next value, which must be an extension value, and returns
+    /// its type and data.
+    pub(crate) fn read_raw_ext(&mut self) -> Result<(i8, &'a [u8]), String> {
+        let len = match self.take_array::<1>()?[0] {
+            marker @ 0xc7..=0xc9 => self.read_len(1 << (marker - 0xc7))?,
+            marker @ 0xd4..=0xd8 => 1 << (marker - 0xd4),
+            marker => {
+                return Err(format!(
+                    "expected extension, found marker byte {:#04x}",
+                    marker
+                ))
+            }
+        };
+        let ty = self.take_array::<1>()?[0] as i8;
+        Ok((ty, self.take(len)?))
+    }
+
+    /// Reads the next value and converts it to JSON.
+    pub(crate) fn read_json(&mut self) -> Result<Value, String> {
+        self.read_value(0)
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        match self.bytes.get(self.pos..self.pos.saturating_add(n)) {
+            Some(bytes) => {
+                self.pos += n;
+                Ok(bytes)
+            }
+            None => Err("unexpected end of input".into()),
+        }
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(<[u8; N]>::try_from(self.take(N)?).expect("take returns N bytes"))
+    }
+
+    fn read_len(&mut self, width: usize) -> Result<usize, String> {
+        Ok(match width {
+            1 => usize::from(self.take_array::<1>()?[0]),
+            2 => usize::from(u16::from_be_bytes(self.take_array()?)),
+            _ => usize::try_from(u32::from_be_bytes(self.take_array()?))
+                .map_err(|_| "length out of range")?,
+        })
+    }
+
+    fn read_str(&mut self, len: usize) -> Result<Value, String> {
+        let s = std::str::from_utf8(self.take(len)?).map_err(|_| "invalid UTF-8 in string")?;
+        Ok(Value::String(s.to_owned()))
+    }
+
+    fn read_array(&mut self, len: usize, depth: usize) -> Result<Value, String> {
+        // Don't trust the length for preallocation; each element takes at
+        // least one byte.
+        let mut elems = Vec::with_capacity(len.min(self.bytes.len() - self.pos));
+        for _ in 0..len {
+            elems.push(self.read_value(depth + 1)?);
+        }
+        Ok(Value::Array(elems))
+    }
+
+    fn read_map(&mut self, len: usize, depth: usize) -> Result<Value, String> {
+        let mut map = Map::new();
+        for _ in 0..len {
+            let key = match self.read_value(depth + 1)? {
+                Value::String(key) => key,
+                Value::Number(key) if key.is_i64() || key.is_u64() => key.to_string(),
+                key => return Err(format!("unsupported map key: {}", key)),
+            };
+            map.insert(key, self.read_value(depth + 1)?);
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn read_ext(&mut self, len: usize) -> Result<Value, String> {
+        let ty = self.take_array::<1>()?[0] as i8;
+        let data = self.take(len)?;
+        match ty {
+            // The timestamp extension, as seconds and nanoseconds since the
+            // Unix epoch.
+            -1 => {
+                let (secs, nanos) = match data.len() {
+                    4 => (i64::from(u32::from_be_bytes(data.try_into().unwrap())), 0),
+                    8 => {
+                        let v = u64::from_be_bytes(data.try_into().unwrap());
+                        ((v & 0x3_ffff_ffff) as i64, (v >> 34) as u32)
+                    }
+                    12 => (
+                        i64::from_be_bytes(data[4..].try_into().unwrap()),
+                        u32::from_be_bytes(data[..4].try_into().unwrap()),
+                    ),
+                    n => return Err(format!("invalid timestamp extension length {}", n)),
+                };
+                let ts = NaiveDateTime::from_timestamp_opt(secs, nanos)
+                    .ok_or("timestamp extension out of range")?;
+                Ok(Value::String(
+                    DateTime::<Utc>::from_utc(ts, Utc).to_rfc3339(),
+                ))
+            }
+            ty => Err(format!("unsupported extension type {}", ty)),
+        }
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err("value nested too deeply".into());
+        }
+        let marker = self.take_array::<1>()?[0];
+        match marker {
+            0x00..=0x7f => Ok(Value::from(marker)),
+            0x80..=0x8f => self.read_map(usize::from(marker & 0x0f), depth),
+            0x90..=0x9f => self.read_array(usize::from(marker & 0x0f), depth),
+            0xa0..=0xbf => self.read_str(usize::from(marker & 0x1f)),
+            0xc0 => Ok(Value::Null),
+            0xc2 => Ok(Value::Bool(false)),
+            0xc3 => Ok(Value::Bool(true)),
+            0xc4..=0xc6 => {
+                let len = self.read_len(1 << (marker - 0xc4))?;
+                Ok(bytes_to_json(self.take(len)?))
+            }
+            0xc7..=0xc9 => {
+                let len = self.read_len(1 << (marker - 0xc7))?;
+                self.read_ext(len)
+            }
+            0xca => float_to_json(f64::from(f32::from_be_bytes(self.take_array()?))),
+            0xcb => float_to_json(f64::from_be_bytes(self.take_array()?)),
+            0xcc => Ok(Value::from(self.take_array::<1>()?[0])),
+            0xcd => Ok(Value::from(u16::from_be_bytes(self.take_array()?))),
+            0xce => Ok(Value::from(u32::from_be_bytes(self.take_array()?))),
+            0xcf => Ok(Value::from(u64::from_be_bytes(self.take_array()?))),
+            0xd0 => Ok(Value::from(i8::from_be_bytes(self.take_array()?))),
+            0xd1 => Ok(Value::from(i16::from_be_bytes(self.take_array()?))),
+            0xd2 => Ok(Value::from(i32::from_be_bytes(self.take_array()?))),
+            0xd3 => Ok(Value::from(i64::from_be_bytes(self.take_array()?))),
+            0xd4..=0xd8 => self.read_ext(1 << (marker - 0xd4)),
+            0xd9..=0xdb => {
+                let len = self.read_len(1 << (marker - 0xd9))?;
+                self.read_str(len)
+            }
+            0xdc | 0xdd => {
+                let len = self.read_len(2 << (marker - 0xdc))?;
+                self.read_array(len, depth)
+            }
+            0xde | 0xdf => {
+                let len = self.read_len(2 << (marker - 0xde))?;
+                self.read_map(len, depth)
+            }
+            0xe0..=0xff => Ok(Value::from(marker as i8)),
+            0xc1 => Err("invalid marker byte 0xc1".into()),
+        }
+    }
+}
+
+fn decode_cbor(bytes: &[u8]) -> Result<Value, String> {
+    let value: serde_cbor::Value = serde_cbor::from_slice(bytes).map_err(|e| e.to_string())?;
+    cbor_to_json(value)
+}
+
+fn cbor_to_json(value: serde_cbor::Value) -> Result<Value, String> {
+    use serde_cbor::Value as Cbor;
+
+    Ok(match value {
+        Cbor::Null => Value::Null,
+        Cbor::Bool(b) => Value::Bool(b),
+        Cbor::Integer(i) => Value::Number(integer_to_json(i)?),
+        Cbor::Float(f) => float_to_json(f)?,
+        Cbor::Bytes(bytes) => bytes_to_json(&bytes),
+        Cbor::Text(s) => Value::String(s),
+        Cbor::Array(elems) => Value::Array(
+            elems
+                .into_iter()
+                .map(cbor_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Cbor::Map(entries) => {
+            let mut map = Map::new();
+            for (key, value) in entries {
+                let key = match key {
+                    Cbor::Text(key) => key,
+                    Cbor::Integer(key) => key.to_string(),
+                    key => return Err(format!("unsupported map key: {:?}", key)),
+                };
+                map.insert(key, cbor_to_json(value)?);
+            }
+            Value::Object(map)
+        }
+        Cbor::Tag(_, value) => cbor_to_json(*value)?,
+        value => return Err(format!("unsupported value: {:?}", value)),
+    })
+}
+
+fn integer_to_json(i: i128) -> Result<Number, String> {
+    if let Ok(i) = i64::try_from(i) {
+        Ok(Number::from(i))
+    } else if let Ok(i) = u64::try_from(i) {
+        Ok(Number::from(i))
+    } else {
+        Err(format!("integer {} out of range", i))
+    }
+}
+
+fn float_to_json(f: f64) -> Result<Value, String> {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| format!("float {} cannot be represented in jsonb", f))
+}
+
+/// Formats `bytes` like a `bytea` value, e.g. `\x0102`.
+fn bytes_to_json(bytes: &[u8]) -> Value {
+    let mut s = String::with_capacity(2 + bytes.len() * 2);
+    s.push_str("\\x");
+    for b in bytes {
+        write!(s, "{:02x}", b).unwrap();
+    }
+    Value::String(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{decode_cbor, decode_msgpack};
+
+    #[test]
+    fn test_decode_msgpack() {
+        // {"a": 1, "b": [true, nil, -1, 1.5], 7: "x", "bin": 0x0102}
+        let bytes = [
+            0x84, 0xa1, b'a', 0x01, 0xa1, b'b', 0x94, 0xc3, 0xc0, 0xff, 0xcb, 0x3f, 0xf8, 0, 0, 0,
+            0, 0, 0, 0x07, 0xa1, b'x', 0xa3, b'b', b'i', b'n', 0xc4, 0x02, 0x01, 0x02,
+        ];
+        assert_eq!(
+            decode_msgpack(&bytes),
+            Ok(json!({"a": 1, "b": [true, null, -1, 1.5], "7": "x", "bin": "\\x0102"}))
+        );
+        assert_eq!(
+            decode_msgpack(&[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Ok(json!(u64::MAX))
+        );
+        assert_eq!(
+            decode_msgpack(&[0xd6, 0xff, 0, 0, 0, 0]),
+            Ok(json!("1970-01-01T00:00:00+00:00"))
+        );
+    }
+
+    #[test]
+    fn test_decode_msgpack_errors() {
+        assert!(decode_msgpack(&[]).is_err());
+        assert!(decode_msgpack(&[0xc1]).is_err());
+        assert!(decode_msgpack(&[0x01, 0x02]).is_err());
+        assert!(decode_msgpack(&[0xa2, b'a']).is_err());
+        assert!(decode_msgpack(&[0xdd, 0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(decode_msgpack(&[0x91; 200]).is_err());
+        assert!(decode_msgpack(&[0x81, 0xc0, 0xc0]).is_err());
+    }
+
+    #[test]
+    fn test_decode_cbor() {
+        // {"a": 1, "b": [true, null, -1, 1.5], 7: "x", "bin": h'0102'}
+        let bytes = [
+            0xa4, 0x61, b'a', 0x01, 0x61, b'b', 0x84, 0xf5, 0xf6, 0x20, 0xf9, 0x3e, 0x00, 0x07,
+            0x61, b'x', 0x63, b'b', b'i', b'n', 0x42, 0x01, 0x02,
+        ];
+        assert_eq!(
+            decode_cbor(&bytes),
+            Ok(json!({"a": 1, "b": [true, null, -1, 1.5], "7": "x", "bin": "\\x0102"}))
+        );
+        assert!(decode_cbor(&[0x01, 0x02]).is_err());
+        assert!(decode_cbor(&[0xf9, 0x7c, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of events in the Common Event Format (CEF).
+//!
+//! A CEF event looks like
+//! `CEF:Version|Device Vendor|Device Product|Device Version|Signature ID|Name|Severity|Extension`,
+//! where the extension is a list of space separated `key=value` pairs whose
+//! values may themselves contain spaces. Events are often prefixed with a
+//! syslog header, so anything before the `CEF:` marker is skipped.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use mz_dataflow_types::DecodeError;
+use mz_repr::{Datum, Row};
+
+#[derive(Debug, Default)]
+pub struct CefDecoderState {
+    row_buf: Row,
+}
+
+impl CefDecoderState {
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeError> {
+        let s = std::str::from_utf8(bytes)
+            .map_err(|_| DecodeError::Text("Failed to decode UTF-8".to_string()))?;
+        let event = parse_cef(s).map_err(DecodeError::Text)?;
+
+        let mut packer = self.row_buf.packer();
+        packer.push(Datum::Int32(event.version));
+        for field in &event.header {
+            packer.push(Datum::String(field));
+        }
+        packer.push_dict(event.extensions.iter().map(|(k, v)| (*k, Datum::String(v))));
+        Ok(Some(self.row_buf.clone()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct CefEvent<'a> {
+    version: i32,
+    /// The device vendor, device product, device version, signature ID, name
+    /// and severity, in that order.
+    header: Vec<Cow<'a, str>>,
+    /// The extension fields. If a key appears more than once the last value
+    /// wins.
+    extensions: BTreeMap<&'a str, Cow<'a, str>>,
+}
+
+fn parse_cef(line: &str) -> Result<CefEvent, String> {
+    let start = line.find("CEF:").ok_or("expected CEF: marker in event")?;
+    let mut rest = &line[start + 4..];
+
+    let mut header = Vec::with_capacity(7);
+    while header.len() < 7 {
+        let end = find_unescaped(rest, b'|').ok_or("truncated CEF header")?;
+        header.push(unescape(&rest[..end], &['|', '\\'], false));
+        rest = &rest[end + 1..];
+    }
+    let version = header.remove(0);
+    let version = version
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid CEF version: {}", version))?;
+
+    Ok(CefEvent {
+        version,
+        header,
+        extensions: parse_extensions(rest)?,
+    })
+}
+
+/// Parses the `key=value` pairs of a CEF extension.
+///
+/// Values may contain unescaped spaces, so a value only ends where the next
+/// key starts, i.e. at the last space before the next unescaped `=`.
+fn parse_extensions(extension: &str) -> Result<BTreeMap<&str, Cow<str>>, String> {
+    fn is_key_byte(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-' | b'[' | b']')
+    }
+
+    let bytes = extension.as_bytes();
+    // The `(start, end)` positions of each key; the `=` is at `end`.
+    let mut keys = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'=' => {
+                let mut start = i;
+                while start > 0 && is_key_byte(bytes[start - 1]) {
+                    start -= 1;
+                }
+                if start < i && (start == 0 || bytes[start - 1] == b' ') {
+                    keys.push((start, i));
+                }
+            }
+            _ => (),
+        }
+        i += 1;
+    }
+
+    let leading = match keys.first() {
+        Some((start, _)) => &extension[..*start],
+        None => extension,
+    };
+    if !leading.trim().is_empty() {
+        return Err(format!("invalid CEF extension: {}", extension));
+    }
+
+    let mut extensions = BTreeMap::new();
+    for (i, (start, end)) in keys.iter().enumerate() {
+        let value_end = keys.get(i + 1).map_or(bytes.len(), |(next, _)| *next);
+        let value = extension[end + 1..value_end].trim_end_matches(' ');
+        extensions.insert(
+            &extension[*start..*end],
+            unescape(value, &['=', '\\'], true),
+        );
+    }
+    Ok(extensions)
+}
+
+/// Returns the position of the first occurrence of `needle` in `s` that is
+/// not escaped with a backslash.
+fn find_unescaped(s: &str, needle: u8) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b if b == needle => return Some(i),
+            _ => (),
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Removes the backslash from escaped `chars`. If `newlines` is set, `\n` and
+/// `\r` are unescaped too. Other backslashes are taken literally.
+fn unescape<'a>(s: &'a str, chars: &[char], newlines: bool) -> Cow<'a, str> {
+    if !s.contains('\\') {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut iter = s.chars().peekable();
+    while let Some(c) = iter.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match iter.peek() {
+            Some(&next) if chars.contains(&next) => out.push(next),
+            Some(&'n') if newlines => out.push('\n'),
+            Some(&'r') if newlines => out.push('\r'),
+            _ => {
+                out.push('\\');
+                continue;
+            }
+        }
+        iter.next();
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse_cef, CefEvent};
+
+    fn event<'a>(
+        version: i32,
+        header: &[&'a str],
+        extensions: &[(&'a str, &'a str)],
+    ) -> CefEvent<'a> {
+        CefEvent {
+            version,
+            header: header.iter().map(|s| (*s).into()).collect(),
+            extensions: BTreeMap::from_iter(extensions.iter().map(|(k, v)| (*k, (*v).into()))),
+        }
+    }
+
+    #[test]
+    fn test_parse_cef() {
+        assert_eq!(
+            parse_cef(
+                r"Sep 19 08:26:10 host CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232 msg=Detected a threat. No action needed."
+            ),
+            Ok(event(
+                0,
+                &[
+                    "Security",
+                    "threatmanager",
+                    "1.0",
+                    "100",
+                    "worm successfully stopped",
+                    "10"
+                ],
+                &[
+                    ("src", "10.0.0.1"),
+                    ("dst", "2.1.2.2"),
+                    ("spt", "1232"),
+                    ("msg", "Detected a threat. No action needed."),
+                ],
+            ))
+        );
+        assert_eq!(
+            parse_cef(
+                r"CEF:1|Ven\|dor|Pro\\duct|2|sig|name|High|path=C:\\dir file\=x.txt act=blocked\nagain"
+            ),
+            Ok(event(
+                1,
+                &["Ven|dor", r"Pro\duct", "2", "sig", "name", "High"],
+                &[("path", r"C:\dir file=x.txt"), ("act", "blocked\nagain")],
+            ))
+        );
+        assert_eq!(
+            parse_cef("CEF:0|a|b|c|d|e|f|"),
+            Ok(event(0, &["a", "b", "c", "d", "e", "f"], &[]))
+        );
+    }
+
+    #[test]
+    fn test_parse_cef_errors() {
+        assert!(parse_cef("not an event").is_err());
+        assert!(parse_cef("CEF:0|a|b|c").is_err());
+        assert!(parse_cef("CEF:x|a|b|c|d|e|f|").is_err());
+        assert!(parse_cef("CEF:0|a|b|c|d|e|f|junk src=1").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of [logfmt](https://brandur.org/logfmt) formatted lines.
+//!
+//! Each line is a sequence of whitespace separated `key=value` pairs. Values
+//! may be double-quoted, in which case they may contain whitespace and the
+//! usual backslash escapes. A key without a `=` is a bare key and decodes to
+//! JSON `null`. When a key appears more than once the last value wins.
+
+use std::borrow::Cow;
+
+use mz_dataflow_types::DecodeError;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::Row;
+
+#[derive(Debug, Default)]
+pub struct LogfmtDecoderState {
+    row_buf: Row,
+}
+
+impl LogfmtDecoderState {
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeError> {
+        let s = std::str::from_utf8(bytes)
+            .map_err(|_| DecodeError::Text("Failed to decode UTF-8".to_string()))?;
+        let pairs = parse_logfmt(s).map_err(DecodeError::Text)?;
+
+        let mut object = serde_json::Map::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            let value = match value {
+                Some(value) => serde_json::Value::String(value.into_owned()),
+                None => serde_json::Value::Null,
+            };
+            object.insert(key.into_owned(), value);
+        }
+
+        let mut packer = self.row_buf.packer();
+        JsonbPacker::new(&mut packer)
+            .pack_serde_json(serde_json::Value::Object(object))
+            .map_err(|e| DecodeError::Text(format!("Failed to pack logfmt line: {}", e)))?;
+        Ok(Some(self.row_buf.clone()))
+    }
+}
+
+/// Splits a logfmt line into its key-value pairs, in the order they appear.
+///
+/// Bare keys have a value of `None`. Unquoted values are borrowed from `line`;
+/// quoted values are only allocated if they contain escape sequences.
+fn parse_logfmt(line: &str) -> Result<Vec<(Cow<str>, Option<Cow<str>>)>, String> {
+    let mut pairs = vec![];
+    let mut chars = line.char_indices().peekable();
+
+    loop {
+        // Skip the whitespace between pairs.
+        while matches!(chars.peek(), Some((_, c)) if c.is_whitespace()) {
+            chars.next();
+        }
+        let key_start = match chars.peek() {
+            None => break,
+            Some((_, '=')) | Some((_, '"')) => {
+                let (pos, c) = chars.next().unwrap();
+                return Err(format!(
+                    "unexpected '{}' at position {}, expected key",
+                    c, pos
+                ));
+            }
+            Some((pos, _)) => *pos,
+        };
+
+        let mut key_end = line.len();
+        while let Some((pos, c)) = chars.peek() {
+            if *c == '=' || *c == '"' || c.is_whitespace() {
+                key_end = *pos;
+                break;
+            }
+            chars.next();
+        }
+        let key = Cow::Borrowed(&line[key_start..key_end]);
+
+        match chars.peek() {
+            Some((_, '=')) => {
+                chars.next();
+            }
+            Some((pos, '"')) => {
+                return Err(format!("unexpected '\"' at position {} in key", pos));
+            }
+            // A key without a value, either at the end of the line or followed
+            // by whitespace.
+            _ => {
+                pairs.push((key, None));
+                continue;
+            }
+        }
+
+        let value = match chars.peek() {
+            Some((_, '"')) => {
+                let (quote_pos, _) = chars.next().unwrap();
+                let mut escaped: Option<String> = None;
+                let value_start = quote_pos + 1;
+                let mut value_end = None;
+                while let Some((pos, c)) = chars.next() {
+                    match c {
+                        '"' => {
+                            value_end = Some(pos);
+                            break;
+                        }
+                        '\\' => {
+                            let buf =
+                                escaped.get_or_insert_with(|| line[value_start..pos].to_string());
+                            match chars.next() {
+                                Some((_, '"')) => buf.push('"'),
+                                Some((_, '\\')) => buf.push('\\'),
+                                Some((_, 'n')) => buf.push('\n'),
+                                Some((_, 'r')) => buf.push('\r'),
+                                Some((_, 't')) => buf.push('\t'),
+                                Some((pos, c)) => {
+                                    return Err(format!(
+                                        "invalid escape sequence '\\{}' at position {}",
+                                        c, pos
+                                    ))
+                                }
+                                None => break,
+                            }
+                        }
+                        c => {
+                            if let Some(buf) = escaped.as_mut() {
+                                buf.push(c);
+                            }
+                        }
+                    }
+                }
+                let value_end = value_end.ok_or_else(|| {
+                    format!(
+                        "unterminated quoted value starting at position {}",
+                        quote_pos
+                    )
+                })?;
+                match escaped {
+                    Some(buf) => Cow::Owned(buf),
+                    None => Cow::Borrowed(&line[value_start..value_end]),
+                }
+            }
+            _ => {
+                let value_start = chars.peek().map(|(pos, _)| *pos).unwrap_or(line.len());
+                let mut value_end = line.len();
+                while let Some((pos, c)) = chars.peek() {
+                    if c.is_whitespace() {
+                        value_end = *pos;
+                        break;
+                    }
+                    if *c == '"' {
+                        return Err(format!("unexpected '\"' at position {} in value", pos));
+                    }
+                    chars.next();
+                }
+                Cow::Borrowed(&line[value_start..value_end])
+            }
+        };
+        pairs.push((key, Some(value)));
+    }
+
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_logfmt;
+
+    fn parse(line: &str) -> Result<Vec<(String, Option<String>)>, String> {
+        Ok(parse_logfmt(line)?
+            .into_iter()
+            .map(|(k, v)| (k.into_owned(), v.map(|v| v.into_owned())))
+            .collect())
+    }
+
+    fn pair(k: &str, v: Option<&str>) -> (String, Option<String>) {
+        (k.to_string(), v.map(|v| v.to_string()))
+    }
+
+    #[test]
+    fn test_parse_logfmt() {
+        assert_eq!(
+            parse(r#"level=info msg="request done" duration=12ms"#),
+            Ok(vec![
+                pair("level", Some("info")),
+                pair("msg", Some("request done")),
+                pair("duration", Some("12ms")),
+            ])
+        );
+        assert_eq!(
+            parse("  debug  path=/a=b empty= "),
+            Ok(vec![
+                pair("debug", None),
+                pair("path", Some("/a=b")),
+                pair("empty", Some("")),
+            ])
+        );
+        assert_eq!(
+            parse(r#"msg="say \"hi\"\n" tab="\t""#),
+            Ok(vec![
+                pair("msg", Some("say \"hi\"\n")),
+                pair("tab", Some("\t")),
+            ])
+        );
+        assert_eq!(parse(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_logfmt_errors() {
+        assert!(parse("=value").is_err());
+        assert!(parse(r#"msg="unterminated"#).is_err());
+        assert!(parse(r#"msg=a"b"#).is_err());
+        assert!(parse(r#"msg="\q""#).is_err());
+    }
+}
@@ -25,25 +25,39 @@ use timely::scheduling::SyncActivator;
 
 use mz_dataflow_types::{
     sources::{
-        encoding::{AvroEncoding, AvroOcfEncoding, DataEncoding, RegexEncoding},
+        encoding::{
+            AvroEncoding, AvroOcfEncoding, DataEncoding, InvalidJsonPolicy, JsonEncoding,
+            RegexEncoding,
+        },
         IncludedColumnSource, SourceEnvelope,
     },
     DecodeError, LinearOperator,
 };
 use mz_interchange::avro::ConfluentAvroResolver;
+use mz_repr::adt::jsonb::JsonbPacker;
 use mz_repr::Datum;
 use mz_repr::{Diff, Row, Timestamp};
 use tracing::error;
 
+use self::access_log::AccessLogDecoderState;
 use self::avro::AvroDecoderState;
+use self::binary_json::{CborDecoderState, MsgpackDecoderState};
+use self::cef::CefDecoderState;
 use self::csv::CsvDecoderState;
+use self::logfmt::LogfmtDecoderState;
 use self::protobuf::ProtobufDecoderState;
+use self::syslog::SyslogDecoderState;
 use crate::metrics::Metrics;
 use crate::source::{DecodeResult, SourceOutput};
 
+mod access_log;
 mod avro;
+pub(crate) mod binary_json;
+mod cef;
 mod csv;
+mod logfmt;
 mod protobuf;
+pub(crate) mod syslog;
 
 pub fn decode_cdcv2<G: Scope<Timestamp = Timestamp>>(
     stream: &Stream<G, SourceOutput<Option<Vec<u8>>, Option<Vec<u8>>>>,
@@ -121,6 +135,13 @@ pub(crate) enum PreDelimitedFormat {
     Text,
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
+    Logfmt(LogfmtDecoderState),
+    Json(InvalidJsonPolicy, Row),
+    Syslog(SyslogDecoderState),
+    Cef(CefDecoderState),
+    AccessLog(AccessLogDecoderState),
+    Msgpack(MsgpackDecoderState),
+    Cbor(CborDecoderState),
 }
 
 impl PreDelimitedFormat {
@@ -148,6 +169,30 @@ impl PreDelimitedFormat {
                 Ok(Some(row_buf.clone()))
             }
             PreDelimitedFormat::Protobuf(pb) => pb.get_value(bytes).transpose(),
+            PreDelimitedFormat::Logfmt(logfmt) => logfmt.decode(bytes),
+            PreDelimitedFormat::Json(invalid, row_buf) => {
+                let result = JsonbPacker::new(&mut row_buf.packer()).pack_slice(bytes);
+                match result {
+                    Ok(()) => Ok(Some(row_buf.clone())),
+                    Err(e) => match invalid {
+                        InvalidJsonPolicy::Error => {
+                            Err(DecodeError::Text(format!("Failed to decode JSON: {}", e)))
+                        }
+                        InvalidJsonPolicy::Null => Ok(Some(Row::pack(Some(Datum::Null)))),
+                        InvalidJsonPolicy::Text => {
+                            let s = std::str::from_utf8(bytes).map_err(|_| {
+                                DecodeError::Text("Failed to decode UTF-8".to_string())
+                            })?;
+                            Ok(Some(Row::pack(Some(Datum::String(s)))))
+                        }
+                    },
+                }
+            }
+            PreDelimitedFormat::Syslog(syslog) => syslog.decode(bytes),
+            PreDelimitedFormat::Cef(cef) => cef.decode(bytes),
+            PreDelimitedFormat::AccessLog(access_log) => access_log.decode(bytes),
+            PreDelimitedFormat::Msgpack(msgpack) => msgpack.decode(bytes),
+            PreDelimitedFormat::Cbor(cbor) => cbor.decode(bytes),
         }
     }
 }
@@ -258,7 +303,14 @@ fn get_decoder(
         DataEncoding::Text
         | DataEncoding::Bytes
         | DataEncoding::Protobuf(_)
-        | DataEncoding::Regex(_) => {
+        | DataEncoding::Regex(_)
+        | DataEncoding::Logfmt
+        | DataEncoding::Json(_)
+        | DataEncoding::Syslog
+        | DataEncoding::Cef
+        | DataEncoding::AccessLog(_)
+        | DataEncoding::Msgpack
+        | DataEncoding::Cbor => {
             let after_delimiting = match encoding {
                 DataEncoding::Regex(RegexEncoding { regex }) => {
                     PreDelimitedFormat::Regex(regex.0, Default::default())
@@ -271,6 +323,17 @@ fn get_decoder(
                 }
                 DataEncoding::Bytes => PreDelimitedFormat::Bytes,
                 DataEncoding::Text => PreDelimitedFormat::Text,
+                DataEncoding::Logfmt => PreDelimitedFormat::Logfmt(Default::default()),
+                DataEncoding::Syslog => PreDelimitedFormat::Syslog(Default::default()),
+                DataEncoding::Cef => PreDelimitedFormat::Cef(Default::default()),
+                DataEncoding::Msgpack => PreDelimitedFormat::Msgpack(Default::default()),
+                DataEncoding::Cbor => PreDelimitedFormat::Cbor(Default::default()),
+                DataEncoding::AccessLog(format) => {
+                    PreDelimitedFormat::AccessLog(AccessLogDecoderState::new(format))
+                }
+                DataEncoding::Json(JsonEncoding { invalid }) => {
+                    PreDelimitedFormat::Json(invalid, Default::default())
+                }
                 _ => unreachable!(),
             };
             let inner = if is_connector_delimited {
@@ -302,6 +365,15 @@ fn get_decoder(
         DataEncoding::Postgres => {
             unreachable!("Postgres sources should not go through the general decoding path.")
         }
+        DataEncoding::Prometheus => {
+            unreachable!("Prometheus sources should not go through the general decoding path.")
+        }
+        DataEncoding::Otlp(_) => {
+            unreachable!("OTLP sources should not go through the general decoding path.")
+        }
+        DataEncoding::Fluent => {
+            unreachable!("Fluent sources should not go through the general decoding path.")
+        }
     }
 }
 
@@ -0,0 +1,350 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of syslog messages.
+//!
+//! Both the [RFC 5424] format and the older BSD [RFC 3164] format are
+//! supported. The two are told apart by the version number that RFC 5424
+//! messages carry right after the priority. RFC 3164 is not really a standard,
+//! so its parsing is lenient: anything that doesn't look like a header ends up
+//! in the message.
+//!
+//! [RFC 5424]: https://datatracker.ietf.org/doc/html/rfc5424
+//! [RFC 3164]: https://datatracker.ietf.org/doc/html/rfc3164
+
+use mz_dataflow_types::DecodeError;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::{Datum, Row};
+
+#[derive(Debug, Default)]
+pub struct SyslogDecoderState {
+    row_buf: Row,
+}
+
+impl SyslogDecoderState {
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeError> {
+        let s = std::str::from_utf8(bytes)
+            .map_err(|_| DecodeError::Text("Failed to decode UTF-8".to_string()))?;
+        let message = parse_syslog(s).map_err(DecodeError::Text)?;
+
+        let mut packer = self.row_buf.packer();
+        packer.push(Datum::Int32(message.priority));
+        packer.push(Datum::Int32(message.priority / 8));
+        packer.push(Datum::Int32(message.priority % 8));
+        for field in [
+            message.timestamp,
+            message.hostname,
+            message.app_name,
+            message.procid,
+            message.msgid,
+        ] {
+            packer.push(Datum::from(field));
+        }
+        match message.structured_data {
+            Some(structured_data) => JsonbPacker::new(&mut packer)
+                .pack_serde_json(structured_data)
+                .map_err(|e| DecodeError::Text(format!("Failed to pack structured data: {}", e)))?,
+            None => packer.push(Datum::Null),
+        }
+        packer.push(Datum::from(message.message));
+        Ok(Some(self.row_buf.clone()))
+    }
+}
+
+/// The parts of a syslog message. Absent and `-` (nil) fields are `None`.
+#[derive(Debug, PartialEq)]
+struct SyslogMessage<'a> {
+    priority: i32,
+    timestamp: Option<&'a str>,
+    hostname: Option<&'a str>,
+    app_name: Option<&'a str>,
+    procid: Option<&'a str>,
+    msgid: Option<&'a str>,
+    /// A JSON object mapping each SD-ID to an object of its parameters.
+    structured_data: Option<serde_json::Value>,
+    message: Option<&'a str>,
+}
+
+fn parse_syslog(line: &str) -> Result<SyslogMessage, String> {
+    let rest = line
+        .strip_prefix('<')
+        .ok_or("expected '<' at start of syslog message")?;
+    let end = rest.find('>').ok_or("unterminated syslog priority")?;
+    let priority = &rest[..end];
+    let priority = match priority.parse() {
+        Ok(p @ 0..=191)
+            if (1..=3).contains(&end) && priority.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            p
+        }
+        _ => return Err(format!("invalid syslog priority: {}", priority)),
+    };
+    let rest = &rest[end + 1..];
+
+    let version_len = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if version_len > 0 && rest[version_len..].starts_with(' ') {
+        parse_rfc5424(priority, &rest[version_len + 1..])
+    } else {
+        Ok(parse_rfc3164(priority, rest))
+    }
+}
+
+/// Parses the part of an RFC 5424 message that follows the version, i.e.
+/// `TIMESTAMP HOSTNAME APP-NAME PROCID MSGID STRUCTURED-DATA [MSG]`.
+fn parse_rfc5424(priority: i32, header: &str) -> Result<SyslogMessage, String> {
+    fn nil(field: &str) -> Option<&str> {
+        if field == "-" {
+            None
+        } else {
+            Some(field)
+        }
+    }
+
+    let fields: Vec<_> = header.splitn(6, ' ').collect();
+    let (timestamp, hostname, app_name, procid, msgid, rest) = match fields[..] {
+        [timestamp, hostname, app_name, procid, msgid, rest] => {
+            (timestamp, hostname, app_name, procid, msgid, rest)
+        }
+        _ => return Err("truncated RFC 5424 syslog header".into()),
+    };
+
+    let (structured_data, rest) = if let Some(rest) = rest.strip_prefix('-') {
+        (None, rest)
+    } else if rest.starts_with('[') {
+        let (structured_data, rest) = parse_structured_data(rest)?;
+        (Some(structured_data), rest)
+    } else {
+        return Err("expected structured data or '-' in syslog message".into());
+    };
+
+    let message = if rest.is_empty() {
+        None
+    } else if let Some(message) = rest.strip_prefix(' ') {
+        Some(message.strip_prefix('\u{feff}').unwrap_or(message))
+    } else {
+        return Err("expected space after structured data in syslog message".into());
+    };
+
+    Ok(SyslogMessage {
+        priority,
+        timestamp: nil(timestamp),
+        hostname: nil(hostname),
+        app_name: nil(app_name),
+        procid: nil(procid),
+        msgid: nil(msgid),
+        structured_data,
+        message,
+    })
+}
+
+/// Parses one or more `[SD-ID PARAM-NAME="PARAM-VALUE" ...]` elements from the
+/// start of `s`, returning them along with what remains of `s`.
+fn parse_structured_data(mut s: &str) -> Result<(serde_json::Value, &str), String> {
+    let mut elements = serde_json::Map::new();
+    while let Some(element) = s.strip_prefix('[') {
+        let id_end = element
+            .find(|c: char| c == ' ' || c == ']')
+            .ok_or("unterminated structured data element")?;
+        let id = &element[..id_end];
+        let mut params = serde_json::Map::new();
+        let mut rest = &element[id_end..];
+        while let Some(param) = rest.strip_prefix(' ') {
+            let name_end = param
+                .find("=\"")
+                .ok_or_else(|| format!("invalid parameter in structured data element {}", id))?;
+            let name = &param[..name_end];
+            let param = &param[name_end + 2..];
+            // Only `"`, `\` and `]` can be escaped; other backslashes are
+            // taken literally.
+            let mut value = String::new();
+            let mut value_end = None;
+            let mut chars = param.char_indices().peekable();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        value_end = Some(i);
+                        break;
+                    }
+                    '\\' => match chars.peek() {
+                        Some((_, c)) if matches!(*c, '"' | '\\' | ']') => {
+                            value.push(*c);
+                            chars.next();
+                        }
+                        _ => value.push('\\'),
+                    },
+                    c => value.push(c),
+                }
+            }
+            let value_end = value_end.ok_or_else(|| {
+                format!("unterminated value for structured data parameter {}", name)
+            })?;
+            params.insert(name.to_owned(), serde_json::Value::String(value));
+            rest = &param[value_end + 1..];
+        }
+        s = rest
+            .strip_prefix(']')
+            .ok_or_else(|| format!("unterminated structured data element {}", id))?;
+        elements.insert(id.to_owned(), serde_json::Value::Object(params));
+    }
+    Ok((serde_json::Value::Object(elements), s))
+}
+
+/// Parses the part of an RFC 3164 message that follows the priority, i.e.
+/// `TIMESTAMP HOSTNAME TAG[PID]: MSG`.
+fn parse_rfc3164(priority: i32, mut rest: &str) -> SyslogMessage {
+    let mut message = SyslogMessage {
+        priority,
+        timestamp: None,
+        hostname: None,
+        app_name: None,
+        procid: None,
+        msgid: None,
+        structured_data: None,
+        message: None,
+    };
+
+    // The hostname is only recognized after a timestamp, since without one
+    // the first word is as likely to be a tag or part of the message.
+    if let Some(timestamp) = rest.get(..15).filter(|ts| is_rfc3164_timestamp(ts)) {
+        if let Some((hostname, after)) =
+            rest[15..].strip_prefix(' ').and_then(|s| s.split_once(' '))
+        {
+            message.timestamp = Some(timestamp);
+            message.hostname = Some(hostname);
+            rest = after;
+        }
+    }
+
+    let tag_end = rest
+        .find(|c: char| c == '[' || c == ':' || c.is_whitespace())
+        .unwrap_or(rest.len());
+    let (tag, after_tag) = rest.split_at(tag_end);
+    let (procid, after_tag) = match after_tag.strip_prefix('[').and_then(|s| s.split_once(']')) {
+        Some((procid, after)) => (Some(procid), after),
+        None => (None, after_tag),
+    };
+    match after_tag.strip_prefix(':') {
+        Some(after) if !tag.is_empty() => {
+            message.app_name = Some(tag);
+            message.procid = procid;
+            message.message = Some(after.strip_prefix(' ').unwrap_or(after));
+        }
+        _ => message.message = Some(rest),
+    }
+    message
+}
+
+/// Reports whether `ts` is a timestamp like `Oct 11 22:14:15`, where days
+/// before the 10th are padded with a space.
+fn is_rfc3164_timestamp(ts: &str) -> bool {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let b = ts.as_bytes();
+    let digits = |range: std::ops::Range<usize>| b[range].iter().all(u8::is_ascii_digit);
+    b.len() == 15
+        && ts.get(..3).map_or(false, |month| MONTHS.contains(&month))
+        && b[3] == b' '
+        && (b[4] == b' ' || b[4].is_ascii_digit())
+        && digits(5..6)
+        && b[6] == b' '
+        && digits(7..9)
+        && b[9] == b':'
+        && digits(10..12)
+        && b[12] == b':'
+        && digits(13..15)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{parse_syslog, SyslogMessage};
+
+    #[test]
+    fn test_parse_rfc5424() {
+        assert_eq!(
+            parse_syslog(
+                r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="Appli\"cation\]"][meta seq="1"] An application event"#
+            ),
+            Ok(SyslogMessage {
+                priority: 165,
+                timestamp: Some("2003-10-11T22:14:15.003Z"),
+                hostname: Some("mymachine.example.com"),
+                app_name: Some("evntslog"),
+                procid: None,
+                msgid: Some("ID47"),
+                structured_data: Some(json!({
+                    "exampleSDID@32473": {"iut": "3", "eventSource": "Appli\"cation]"},
+                    "meta": {"seq": "1"},
+                })),
+                message: Some("An application event"),
+            })
+        );
+        assert_eq!(
+            parse_syslog("<34>1 - - - - - -"),
+            Ok(SyslogMessage {
+                priority: 34,
+                timestamp: None,
+                hostname: None,
+                app_name: None,
+                procid: None,
+                msgid: None,
+                structured_data: None,
+                message: None,
+            })
+        );
+        assert_eq!(
+            parse_syslog("<34>1 - - su 12 - - \u{feff}BOM")
+                .unwrap()
+                .message,
+            Some("BOM")
+        );
+    }
+
+    #[test]
+    fn test_parse_rfc3164() {
+        assert_eq!(
+            parse_syslog("<34>Oct 11 22:14:15 mymachine su[230]: 'su root' failed"),
+            Ok(SyslogMessage {
+                priority: 34,
+                timestamp: Some("Oct 11 22:14:15"),
+                hostname: Some("mymachine"),
+                app_name: Some("su"),
+                procid: Some("230"),
+                msgid: None,
+                structured_data: None,
+                message: Some("'su root' failed"),
+            })
+        );
+        assert_eq!(
+            parse_syslog("<13>just some text: here"),
+            Ok(SyslogMessage {
+                priority: 13,
+                timestamp: None,
+                hostname: None,
+                app_name: None,
+                procid: None,
+                msgid: None,
+                structured_data: None,
+                message: Some("just some text: here"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_syslog_errors() {
+        assert!(parse_syslog("no priority").is_err());
+        assert!(parse_syslog("<192>1 - - - - - -").is_err());
+        assert!(parse_syslog("<+1>message").is_err());
+        assert!(parse_syslog("<34>1 - - -").is_err());
+        assert!(parse_syslog("<34>1 - - - - - [unterminated").is_err());
+        assert!(parse_syslog(r#"<34>1 - - - - - [id a="b] msg"#).is_err());
+    }
+}
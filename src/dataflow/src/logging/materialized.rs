@@ -28,6 +28,7 @@ use crate::activator::RcActivator;
 use crate::arrangement::manager::RowSpine;
 use crate::arrangement::KeysValsHandle;
 use crate::replay::MzReplay;
+use mz_dataflow_types::SourceErrorDetails;
 use mz_expr::{permutation_for_arrangement, GlobalId, MirScalarExpr, SourceInstanceId};
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::{Datum, DatumVec, Diff, Row, Timestamp};
@@ -58,6 +59,28 @@ pub enum MaterializedEvent {
     },
     /// Peek command, true for install and false for retire.
     Peek(Peek, bool),
+    /// An error produced by a source, true when it becomes one of the source's most recent errors
+    /// and false when it is retracted again.
+    SourceError {
+        /// Source identifier
+        source_id: SourceInstanceId,
+        /// The timestamp at which the error was emitted
+        timestamp: Timestamp,
+        /// The error itself
+        error: SourceErrorDetails,
+        /// Whether the error is recorded or retracted
+        is_recent: bool,
+    },
+    /// Bytes and rows ingested by a source since it was last logged, or their negated totals when
+    /// it is dropped.
+    SourceIngestion {
+        /// Source identifier
+        source_id: SourceInstanceId,
+        /// Number of bytes ingested
+        bytes: i64,
+        /// Number of rows ingested
+        rows: i64,
+    },
     /// Tracks the source name, id, partition id, and received/ingested offsets
     SourceInfo {
         /// Name of the source
@@ -131,6 +154,8 @@ pub fn construct<A: Allocate>(
         let (mut kafka_source_statistics_out, kafka_source_statistics) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
         let (mut peek_duration_out, peek_duration) = demux.new_output();
+        let (mut source_errors_out, source_errors) = demux.new_output();
+        let (mut source_ingestion_out, source_ingestion) = demux.new_output();
         let (mut source_info_out, source_info) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
@@ -144,6 +169,8 @@ pub fn construct<A: Allocate>(
                 let mut kafka_source_statistics = kafka_source_statistics_out.activate();
                 let mut peek = peek_out.activate();
                 let mut peek_duration = peek_duration_out.activate();
+                let mut source_errors = source_errors_out.activate();
+                let mut source_ingestion = source_ingestion_out.activate();
                 let mut source_info = source_info_out.activate();
 
                 input.for_each(|time, data| {
@@ -156,6 +183,8 @@ pub fn construct<A: Allocate>(
                         kafka_source_statistics.session(&time);
                     let mut peek_session = peek.session(&time);
                     let mut peek_duration_session = peek_duration.session(&time);
+                    let mut source_errors_session = source_errors.session(&time);
+                    let mut source_ingestion_session = source_ingestion.session(&time);
                     let mut source_info_session = source_info.session(&time);
 
                     for (time, worker, datum) in demux_buffer.drain(..) {
@@ -270,6 +299,30 @@ pub fn construct<A: Allocate>(
                                     }
                                 }
                             }
+                            MaterializedEvent::SourceError {
+                                source_id,
+                                timestamp,
+                                error,
+                                is_recent,
+                            } => {
+                                let diff = if is_recent { 1 } else { -1 };
+                                source_errors_session.give((
+                                    (source_id, worker, timestamp, error),
+                                    time_ms,
+                                    diff,
+                                ));
+                            }
+                            MaterializedEvent::SourceIngestion {
+                                source_id,
+                                bytes,
+                                rows,
+                            } => {
+                                source_ingestion_session.give((
+                                    (source_id, worker),
+                                    time_ms,
+                                    (bytes, rows),
+                                ));
+                            }
                             MaterializedEvent::SourceInfo {
                                 source_name,
                                 source_id,
@@ -332,6 +385,36 @@ pub fn construct<A: Allocate>(
             }
         });
 
+        let source_errors_current = source_errors.as_collection().map({
+            move |(id, worker, timestamp, error)| {
+                let (category, message) = match &error {
+                    SourceErrorDetails::Initialization(e) => ("initialization", e),
+                    SourceErrorDetails::FileIO(e) => ("file_io", e),
+                    SourceErrorDetails::Persistence(e) => ("persistence", e),
+                };
+                Row::pack_slice(&[
+                    Datum::String(&id.source_id.to_string()),
+                    Datum::Int64(id.dataflow_id as i64),
+                    Datum::Int64(worker as i64),
+                    Datum::Int64(timestamp as i64),
+                    Datum::String(category),
+                    Datum::String(message),
+                ])
+            }
+        });
+
+        let source_ingestion_current = source_ingestion.as_collection().count_core().map({
+            move |((id, worker), (bytes, rows))| {
+                Row::pack_slice(&[
+                    Datum::String(&id.source_id.to_string()),
+                    Datum::Int64(id.dataflow_id as i64),
+                    Datum::Int64(worker as i64),
+                    Datum::Int64(bytes),
+                    Datum::Int64(rows),
+                ])
+            }
+        });
+
         let source_info_current = source_info.as_collection().count_core().map({
             move |((name, id, pid), (offset, timestamp))| {
                 Row::pack_slice(&[
@@ -381,6 +464,14 @@ pub fn construct<A: Allocate>(
                 LogVariant::Materialized(MaterializedLog::PeekDuration),
                 peek_duration,
             ),
+            (
+                LogVariant::Materialized(MaterializedLog::SourceErrors),
+                source_errors_current,
+            ),
+            (
+                LogVariant::Materialized(MaterializedLog::SourceIngestion),
+                source_ingestion_current,
+            ),
             (
                 LogVariant::Materialized(MaterializedLog::SourceInfo),
                 source_info_current,
@@ -39,6 +39,13 @@ impl Metrics {
                 PreDelimitedFormat::Text => "text",
                 PreDelimitedFormat::Regex(..) => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
+                PreDelimitedFormat::Logfmt(..) => "logfmt",
+                PreDelimitedFormat::Json(..) => "json",
+                PreDelimitedFormat::Syslog(..) => "syslog",
+                PreDelimitedFormat::Cef(..) => "cef",
+                PreDelimitedFormat::AccessLog(..) => "access_log",
+                PreDelimitedFormat::Msgpack(..) => "msgpack",
+                PreDelimitedFormat::Cbor(..) => "cbor",
             },
         };
         let success_label = if success { "success" } else { "error" };
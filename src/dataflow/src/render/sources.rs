@@ -46,9 +46,10 @@ use crate::server::LocalInput;
 use crate::server::StorageState;
 use crate::source::timestamp::{AssignedTimestamp, SourceTimestamp};
 use crate::source::{
-    self, DecodeResult, FileSourceReader, KafkaSourceReader, KinesisSourceReader,
-    PersistentTimestampBindingsConfig, PostgresSourceReader, PubNubSourceReader, S3SourceReader,
-    SourceConfig,
+    self, DecodeResult, FileSourceReader, FluentSourceReader, KafkaSourceReader,
+    KinesisSourceReader, OtlpSourceReader, PersistentTimestampBindingsConfig, PostgresSourceReader,
+    PrometheusSourceReader, PubNubSourceReader, S3SourceReader, SourceConfig, SyslogSourceReader,
+    TempoSourceReader,
 };
 
 /// A type-level enum that holds one of two types of sources depending on their message type
@@ -233,6 +234,7 @@ where
             metadata_columns,
             ts_frequency,
             timeline: _,
+            retention,
         } => {
             // TODO(benesch): this match arm is hard to follow. Refactor.
 
@@ -285,6 +287,12 @@ where
                 now: storage_state.now.clone(),
                 base_metrics: &storage_state.source_metrics,
                 aws_external_id: storage_state.aws_external_id.clone(),
+                // Sources without an entry cannot be paused, so they get a receiver whose
+                // sender is already gone and that stays unpaused.
+                paused: match storage_state.source_pauses.get(&src_id) {
+                    Some(pause) => pause.subscribe(),
+                    None => tokio::sync::watch::channel(false).1,
+                },
             };
 
             let (mut collection, capability) = if let ExternalSourceConnector::PubNub(
@@ -317,6 +325,76 @@ where
                         .as_collection(),
                 );
 
+                (ok_stream.as_collection(), capability)
+            } else if let ExternalSourceConnector::Prometheus(prometheus_connector) = connector {
+                let source = PrometheusSourceReader::new(uid, prometheus_connector);
+
+                let ((ok_stream, err_stream), capability) =
+                    source::create_source_simple(source_config, source);
+
+                error_collections.push(
+                    err_stream
+                        .map(DataflowError::SourceError)
+                        .pass_through("source-errors")
+                        .as_collection(),
+                );
+
+                (ok_stream.as_collection(), capability)
+            } else if let ExternalSourceConnector::Otlp(otlp_connector) = connector {
+                let source = OtlpSourceReader::new(uid, otlp_connector);
+
+                let ((ok_stream, err_stream), capability) =
+                    source::create_source_simple(source_config, source);
+
+                error_collections.push(
+                    err_stream
+                        .map(DataflowError::SourceError)
+                        .pass_through("source-errors")
+                        .as_collection(),
+                );
+
+                (ok_stream.as_collection(), capability)
+            } else if let ExternalSourceConnector::Tempo(tempo_connector) = connector {
+                let source = TempoSourceReader::new(uid, tempo_connector);
+
+                let ((ok_stream, err_stream), capability) =
+                    source::create_source_simple(source_config, source);
+
+                error_collections.push(
+                    err_stream
+                        .map(DataflowError::SourceError)
+                        .pass_through("source-errors")
+                        .as_collection(),
+                );
+
+                (ok_stream.as_collection(), capability)
+            } else if let ExternalSourceConnector::Syslog(syslog_connector) = connector {
+                let source = SyslogSourceReader::new(uid, syslog_connector);
+
+                let ((ok_stream, err_stream), capability) =
+                    source::create_source_simple(source_config, source);
+
+                error_collections.push(
+                    err_stream
+                        .map(DataflowError::SourceError)
+                        .pass_through("source-errors")
+                        .as_collection(),
+                );
+
+                (ok_stream.as_collection(), capability)
+            } else if let ExternalSourceConnector::Fluent(fluent_connector) = connector {
+                let source = FluentSourceReader::new(uid, fluent_connector);
+
+                let ((ok_stream, err_stream), capability) =
+                    source::create_source_simple(source_config, source);
+
+                error_collections.push(
+                    err_stream
+                        .map(DataflowError::SourceError)
+                        .pass_through("source-errors")
+                        .as_collection(),
+                );
+
                 (ok_stream.as_collection(), capability)
             } else {
                 let ((ok_source, ts_bindings, err_source), capability) = match connector {
@@ -366,6 +444,11 @@ where
                     }
                     ExternalSourceConnector::Postgres(_) => unreachable!(),
                     ExternalSourceConnector::PubNub(_) => unreachable!(),
+                    ExternalSourceConnector::Prometheus(_) => unreachable!(),
+                    ExternalSourceConnector::Otlp(_) => unreachable!(),
+                    ExternalSourceConnector::Tempo(_) => unreachable!(),
+                    ExternalSourceConnector::Syslog(_) => unreachable!(),
+                    ExternalSourceConnector::Fluent(_) => unreachable!(),
                 };
 
                 // Include any source errors.
@@ -648,6 +731,20 @@ where
                 error_collections.push(errors.as_collection());
             };
 
+            // Retract each row once it is older than the retention period. As
+            // with temporal filters, the retraction is emitted right away at a
+            // future time, and arrangements drop both updates as they compact.
+            if let Some(retention) = retention {
+                let retention_ms = u64::try_from(retention.as_millis()).unwrap_or(u64::MAX);
+                collection = collection
+                    .inner
+                    .flat_map(move |(row, time, diff)| {
+                        let expiry = time.saturating_add(retention_ms);
+                        [(row.clone(), time, diff), (row, expiry, -diff)]
+                    })
+                    .as_collection();
+            }
+
             // Flatten the error collections.
             let mut err_collection = match error_collections.len() {
                 0 => Collection::empty(scope),
@@ -168,6 +168,7 @@ pub fn serve_boundary<
                 source_descriptions: HashMap::new(),
                 source_uppers: HashMap::new(),
                 ts_source_mapping: HashMap::new(),
+                source_pauses: HashMap::new(),
                 ts_histories: HashMap::default(),
                 persisted_sources: PersistedSourceManager::new(),
                 unspecified_metrics,
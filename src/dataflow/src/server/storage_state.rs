@@ -14,7 +14,7 @@ use timely::order::PartialOrder;
 use timely::progress::frontier::Antichain;
 use timely::progress::ChangeBatch;
 use timely::worker::Worker as TimelyWorker;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, trace};
 
 use mz_dataflow_types::client::{
@@ -60,6 +60,8 @@ pub struct StorageState {
     pub source_uppers: HashMap<GlobalId, Rc<RefCell<Antichain<mz_repr::Timestamp>>>>,
     /// Handles to external sources, keyed by ID.
     pub ts_source_mapping: HashMap<GlobalId, Vec<Weak<Option<SourceToken>>>>,
+    /// Whether each external source is paused, shared with all of its instances.
+    pub source_pauses: HashMap<GlobalId, watch::Sender<bool>>,
     /// Timestamp data updates for each source.
     pub ts_histories: HashMap<GlobalId, TimestampBindingRc>,
     /// Handles that allow setting the compaction frontier for a persisted source. There can only
@@ -134,7 +136,13 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
                     Some(rt_default)
                 }
                 ExternalSourceConnector::Kafka(_) => Some(rt_default),
-                ExternalSourceConnector::Postgres(_) | ExternalSourceConnector::PubNub(_) => None,
+                ExternalSourceConnector::Postgres(_)
+                | ExternalSourceConnector::PubNub(_)
+                | ExternalSourceConnector::Prometheus(_)
+                | ExternalSourceConnector::Otlp(_)
+                | ExternalSourceConnector::Tempo(_)
+                | ExternalSourceConnector::Syslog(_)
+                | ExternalSourceConnector::Fluent(_) => None,
             }
         } else {
             debug!(
@@ -200,8 +208,8 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
                             );
                         }
                         SourceConnector::External { .. } => {
-                            // Nothing to do at the moment, but in the future
-                            // prepare source ingestion.
+                            let (pause_tx, _) = watch::channel(false);
+                            self.storage_state.source_pauses.insert(source.id, pause_tx);
                         }
                     }
 
@@ -240,6 +248,7 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
                         self.storage_state.reported_frontiers.remove(&id);
                         self.storage_state.ts_histories.remove(&id);
                         self.storage_state.ts_source_mapping.remove(&id);
+                        self.storage_state.source_pauses.remove(&id);
                     } else {
                         if let Some(ts_history) = self.storage_state.ts_histories.get_mut(&id) {
                             ts_history.set_compaction_frontier(frontier.borrow());
@@ -339,6 +348,14 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
                     }
                 }
             }
+
+            StorageCommand::PauseSources(list) => {
+                for (id, paused) in list {
+                    if let Some(pause_tx) = self.storage_state.source_pauses.get(&id) {
+                        pause_tx.send_replace(paused);
+                    }
+                }
+            }
         }
     }
 
@@ -0,0 +1,592 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Fluent sources.
+//!
+//! A Fluent source accepts the events that Fluentd and Fluent Bit forward
+//! with the [forward protocol], i.e. MessagePack arrays over TCP in any of its
+//! message, forward, packed forward and compressed packed forward modes. Each
+//! event becomes a row of its tag, time and record. If a message asks for an
+//! acknowledgement, it is sent once the message's events have been ingested.
+//!
+//! The handshake that authenticates senders with a shared key, TLS and the
+//! UDP heartbeat are not supported.
+//!
+//! [forward protocol]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1
+
+use std::convert::Infallible;
+use std::io::Read;
+use std::net::SocketAddr;
+
+use anyhow::bail;
+use async_trait::async_trait;
+use bytes::BytesMut;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::read::MultiGzDecoder;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde_json::{Map, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tracing::{info, info_span, warn, Instrument};
+
+use mz_dataflow_types::{sources::FluentSourceConnector, SourceErrorDetails};
+use mz_expr::SourceInstanceId;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::{Datum, Row};
+
+use crate::decode::binary_json::MsgpackReader;
+use crate::source::util::{ingest, insert_and_wait, record_endpoint, Batch};
+use crate::source::{SimpleSource, SourceError, Timestamper};
+
+/// The maximum length of a message, and of the events it packs once they are
+/// decompressed. Fluentd forwards each of its buffer chunks as one message,
+/// and memory buffer chunks are at most 8 MiB by default; the rest of the
+/// margin is for senders that raise `chunk_limit_size`.
+const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+/// Information required to receive events from Fluentd and Fluent Bit
+pub struct FluentSourceReader {
+    source_id: SourceInstanceId,
+    connector: FluentSourceConnector,
+}
+
+impl FluentSourceReader {
+    /// Constructs a new instance
+    pub fn new(source_id: SourceInstanceId, connector: FluentSourceConnector) -> Self {
+        Self {
+            source_id,
+            connector,
+        }
+    }
+}
+
+#[async_trait]
+impl SimpleSource for FluentSourceReader {
+    async fn start(self, timestamper: &Timestamper) -> Result<(), SourceError> {
+        let source_id = self.source_id;
+        let addr = self.connector.addr;
+        record_endpoint(addr);
+        let listener = TcpListener::bind(addr).await.map_err(|e| SourceError {
+            source_id,
+            error: SourceErrorDetails::Initialization(format!(
+                "unable to listen on {}: {}",
+                addr, e
+            )),
+        })?;
+        info!("fluent source {} listening on {}", source_id, addr);
+
+        let (tx, rx) = mpsc::channel(16);
+        ingest(source_id, timestamper, rx, accept(source_id, listener, tx)).await
+    }
+}
+
+/// Accepts connections and reads their messages until the source is dropped.
+async fn accept(
+    source_id: SourceInstanceId,
+    listener: TcpListener,
+    tx: mpsc::Sender<Batch>,
+) -> Infallible {
+    let mut connections = FuturesUnordered::new();
+    loop {
+        tokio::select! {
+            res = listener.accept() => match res {
+                Ok((stream, peer)) => {
+                    let span = info_span!("connection", %peer);
+                    let connection = handle_connection(stream, peer, tx.clone());
+                    connections.push(connection.instrument(span));
+                }
+                Err(e) => warn!("fluent source {} failed to accept: {}", source_id, e),
+            },
+            Some((peer, res)) = connections.next() => {
+                if let Err(e) = res {
+                    warn!("fluent source {} closed connection from {}: {}", source_id, peer, e);
+                }
+            }
+        }
+    }
+}
+
+/// Reads the messages of a connection until it is closed, and returns the
+/// peer's address along with the reason for closing it, if it is an error.
+async fn handle_connection(
+    stream: TcpStream,
+    peer: SocketAddr,
+    tx: mpsc::Sender<Batch>,
+) -> (SocketAddr, Result<(), anyhow::Error>) {
+    (peer, read_messages(stream, tx).await)
+}
+
+async fn read_messages(
+    mut stream: TcpStream,
+    tx: mpsc::Sender<Batch>,
+) -> Result<(), anyhow::Error> {
+    let mut buf = BytesMut::with_capacity(8 * 1024);
+    let mut scanner = MessageScanner::default();
+    loop {
+        while let Some(len) = scanner.scan(&buf).map_err(anyhow::Error::msg)? {
+            let message = decode_message(&buf.split_to(len)).map_err(anyhow::Error::msg)?;
+            let mut rows = Vec::with_capacity(message.entries.len());
+            for (time, record) in message.entries {
+                rows.push(pack_event(&message.tag, time, record)?);
+            }
+            if !insert_and_wait(&tx, rows).await {
+                // The source is shutting down.
+                return Ok(());
+            }
+            if let Some(chunk) = message.chunk {
+                stream.write_all(&encode_ack(&chunk)).await?;
+            }
+        }
+        if stream.read_buf(&mut buf).await? == 0 {
+            if !buf.is_empty() {
+                bail!("connection closed in the middle of a message");
+            }
+            return Ok(());
+        }
+    }
+}
+
+fn pack_event(tag: &str, time: DateTime<Utc>, record: Value) -> Result<Row, anyhow::Error> {
+    let mut row = Row::default();
+    let mut packer = row.packer();
+    packer.push(Datum::String(tag));
+    packer.push(Datum::TimestampTz(time));
+    JsonbPacker::new(&mut packer).pack_serde_json(record)?;
+    Ok(row)
+}
+
+/// Encodes the `{"ack": chunk}` map that acknowledges a message.
+fn encode_ack(chunk: &str) -> Vec<u8> {
+    let mut ack = vec![0x81, 0xa3];
+    ack.extend(b"ack");
+    match chunk.len() {
+        len @ 0..=0x1f => ack.push(0xa0 | len as u8),
+        len @ 0x20..=0xff => ack.extend([0xd9, len as u8]),
+        len @ 0x100..=0xffff => {
+            ack.push(0xda);
+            ack.extend((len as u16).to_be_bytes());
+        }
+        len => {
+            ack.push(0xdb);
+            ack.extend((len as u32).to_be_bytes());
+        }
+    }
+    ack.extend(chunk.as_bytes());
+    ack
+}
+
+/// Finds where the MessagePack value at the start of a buffer ends, as its
+/// bytes arrive.
+///
+/// Only the headers of values are scanned, and the scan resumes where it
+/// left off, so large messages that arrive in many reads are not rescanned
+/// from the start.
+#[derive(Debug)]
+struct MessageScanner {
+    /// The position of the next value's header.
+    pos: usize,
+    /// The number of values that are yet to be scanned.
+    remaining: u64,
+}
+
+impl Default for MessageScanner {
+    fn default() -> Self {
+        MessageScanner {
+            pos: 0,
+            remaining: 1,
+        }
+    }
+}
+
+impl MessageScanner {
+    /// Returns the length of the value at the start of `buf`, or `None` if
+    /// `buf` ends before the value does. Once a length is returned, the
+    /// scanner starts over for the next value.
+    fn scan(&mut self, buf: &[u8]) -> Result<Option<usize>, String> {
+        while self.remaining > 0 {
+            let marker = match buf.get(self.pos) {
+                Some(marker) => *marker,
+                None => return Ok(None),
+            };
+            // The length of the value's header, the length of the data that
+            // follows it, and the number of values nested in it.
+            let (header_len, data_len, children) = match marker {
+                0x00..=0x7f | 0xc0 | 0xc2 | 0xc3 | 0xe0..=0xff => (1, 0, 0),
+                0x80..=0x8f => (1, 0, 2 * u64::from(marker & 0x0f)),
+                0x90..=0x9f => (1, 0, u64::from(marker & 0x0f)),
+                0xa0..=0xbf => (1, usize::from(marker & 0x1f), 0),
+                0xc4..=0xc6 | 0xc7..=0xc9 | 0xd9..=0xdb => {
+                    // Binary and string values have a length, and extension
+                    // values also have a type.
+                    let (width, type_len) = match marker {
+                        0xc4..=0xc6 => (1 << (marker - 0xc4), 0),
+                        0xc7..=0xc9 => (1 << (marker - 0xc7), 1),
+                        _ => (1 << (marker - 0xd9), 0),
+                    };
+                    match read_len(buf, self.pos + 1, width) {
+                        Some(len) => (1 + width + type_len, len, 0),
+                        None => return Ok(None),
+                    }
+                }
+                0xca | 0xcb => (1, 4 << (marker - 0xca), 0),
+                0xcc..=0xcf => (1, 1 << (marker - 0xcc), 0),
+                0xd0..=0xd3 => (1, 1 << (marker - 0xd0), 0),
+                0xd4..=0xd8 => (2, 1 << (marker - 0xd4), 0),
+                0xdc | 0xdd | 0xde | 0xdf => {
+                    let width = 2 << (marker & 1);
+                    match read_len(buf, self.pos + 1, width) {
+                        Some(len) if marker < 0xde => (1 + width, 0, len as u64),
+                        Some(len) => (1 + width, 0, 2 * len as u64),
+                        None => return Ok(None),
+                    }
+                }
+                0xc1 => return Err("invalid marker byte 0xc1".into()),
+            };
+            let end = self.pos + header_len + data_len;
+            if end > MAX_MESSAGE_LEN {
+                return Err("message too long".into());
+            }
+            if end > buf.len() {
+                return Ok(None);
+            }
+            self.pos = end;
+            self.remaining = self.remaining - 1 + children;
+        }
+        let len = self.pos;
+        *self = MessageScanner::default();
+        Ok(Some(len))
+    }
+}
+
+/// Reads a big-endian length of `width` bytes at `pos`.
+fn read_len(buf: &[u8], pos: usize, width: usize) -> Option<usize> {
+    let bytes = buf.get(pos..pos + width)?;
+    Some(
+        bytes
+            .iter()
+            .fold(0, |len, byte| (len << 8) | usize::from(*byte)),
+    )
+}
+
+/// A forward protocol message.
+#[derive(Debug, PartialEq)]
+struct Message {
+    tag: String,
+    /// The time and record of each event.
+    entries: Vec<(DateTime<Utc>, Value)>,
+    /// The chunk ID to acknowledge the message with, if the sender asked for
+    /// an acknowledgement.
+    chunk: Option<String>,
+}
+
+fn decode_message(bytes: &[u8]) -> Result<Message, String> {
+    let mut reader = MsgpackReader::new(bytes);
+    let len = reader.read_array_len()?;
+    let tag = match reader.read_json()? {
+        Value::String(tag) => tag,
+        tag => return Err(format!("expected tag to be a string, found {}", tag)),
+    };
+
+    // The modes are told apart by the type of the second element: a time
+    // for a single event, an array of events, or events packed into a binary
+    // or string value.
+    let (entries, packed, option_index) = match reader.peek_marker()? {
+        0x90..=0x9f | 0xdc | 0xdd => {
+            let mut entries = vec![];
+            for _ in 0..reader.read_array_len()? {
+                entries.push(read_entry(&mut reader)?);
+            }
+            (entries, None, 2)
+        }
+        0xa0..=0xbf | 0xc4..=0xc6 | 0xd9..=0xdb => (vec![], Some(reader.read_raw_bytes()?), 2),
+        _ => {
+            let time = read_time(&mut reader)?;
+            let record = read_record(&mut reader)?;
+            (vec![(time, record)], None, 3)
+        }
+    };
+    let option = match len.checked_sub(option_index) {
+        Some(0) => Map::new(),
+        Some(1) => match reader.read_json()? {
+            Value::Object(option) => option,
+            Value::Null => Map::new(),
+            option => return Err(format!("expected option to be a map, found {}", option)),
+        },
+        _ => return Err(format!("unexpected message of {} elements", len)),
+    };
+    if !reader.is_empty() {
+        return Err("trailing bytes after message".into());
+    }
+
+    let entries = match packed {
+        None => entries,
+        Some(packed) => {
+            let decompressed;
+            let packed = match option.get("compressed") {
+                None => packed,
+                Some(Value::String(compression)) if compression == "gzip" => {
+                    // Reading one byte past the maximum tells events that
+                    // decompress to exactly the maximum apart from longer ones.
+                    let mut buf = vec![];
+                    MultiGzDecoder::new(packed)
+                        .take(MAX_MESSAGE_LEN as u64 + 1)
+                        .read_to_end(&mut buf)
+                        .map_err(|e| format!("failed to decompress events: {}", e))?;
+                    if buf.len() > MAX_MESSAGE_LEN {
+                        return Err(format!(
+                            "decompressed events exceed {} bytes",
+                            MAX_MESSAGE_LEN
+                        ));
+                    }
+                    decompressed = buf;
+                    &decompressed
+                }
+                Some(compression) => {
+                    return Err(format!("unsupported compression: {}", compression))
+                }
+            };
+            let mut reader = MsgpackReader::new(packed);
+            let mut entries = vec![];
+            while !reader.is_empty() {
+                entries.push(read_entry(&mut reader)?);
+            }
+            entries
+        }
+    };
+
+    let chunk = match option.get("chunk") {
+        None => None,
+        Some(Value::String(chunk)) => Some(chunk.clone()),
+        Some(chunk) => return Err(format!("expected chunk to be a string, found {}", chunk)),
+    };
+
+    Ok(Message {
+        tag,
+        entries,
+        chunk,
+    })
+}
+
+/// Reads an event of the forward modes, i.e. an array of its time and record.
+fn read_entry(reader: &mut MsgpackReader) -> Result<(DateTime<Utc>, Value), String> {
+    let len = reader.read_array_len()?;
+    if len != 2 {
+        return Err(format!("expected event of 2 elements, found {}", len));
+    }
+    Ok((read_time(reader)?, read_record(reader)?))
+}
+
+/// Reads an event time, which is either Fluentd's `EventTime` extension,
+/// with seconds and nanoseconds since the Unix epoch, or seconds since the
+/// Unix epoch.
+fn read_time(reader: &mut MsgpackReader) -> Result<DateTime<Utc>, String> {
+    let (secs, nanos) = match reader.peek_marker()? {
+        0xc7..=0xc9 | 0xd4..=0xd8 => match reader.read_raw_ext()? {
+            (0, data) if data.len() == 8 => (
+                i64::from(u32::from_be_bytes(data[..4].try_into().unwrap())),
+                u32::from_be_bytes(data[4..].try_into().unwrap()),
+            ),
+            (ty, data) => {
+                return Err(format!(
+                    "invalid time extension of type {} and length {}",
+                    ty,
+                    data.len()
+                ))
+            }
+        },
+        _ => match reader.read_json()? {
+            Value::Number(n) if n.is_i64() || n.is_u64() => {
+                (n.as_i64().ok_or("time out of range")?, 0)
+            }
+            // Some senders use floats despite the specification.
+            Value::Number(n) => {
+                let secs = n.as_f64().expect("number is a float");
+                (secs.floor() as i64, (secs.fract() * 1e9) as u32)
+            }
+            time => return Err(format!("expected time, found {}", time)),
+        },
+    };
+    let time = NaiveDateTime::from_timestamp_opt(secs, nanos).ok_or("time out of range")?;
+    Ok(DateTime::from_utc(time, Utc))
+}
+
+fn read_record(reader: &mut MsgpackReader) -> Result<Value, String> {
+    match reader.read_json()? {
+        record @ Value::Object(_) => Ok(record),
+        record => Err(format!("expected record to be a map, found {}", record)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use chrono::{DateTime, TimeZone, Utc};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use serde_json::json;
+
+    use super::{decode_message, encode_ack, Message, MessageScanner, MAX_MESSAGE_LEN};
+
+    /// The `EventTime` of 1600000000 seconds and 7 nanoseconds, and the record
+    /// `{"a": "b"}`, in MessagePack.
+    const TIME: [u8; 10] = [0xd7, 0x00, 0x5f, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x00, 0x07];
+    const RECORD: [u8; 5] = [0x81, 0xa1, b'a', 0xa1, b'b'];
+
+    fn time() -> DateTime<Utc> {
+        Utc.timestamp(1600000000, 7)
+    }
+
+    fn tag() -> Vec<u8> {
+        vec![0xa3, b'a', b'p', b'p']
+    }
+
+    fn chunk_option() -> Vec<u8> {
+        vec![0x81, 0xa5, b'c', b'h', b'u', b'n', b'k', 0xa2, b'i', b'd']
+    }
+
+    fn entry() -> Vec<u8> {
+        [&[0x92][..], &TIME, &RECORD].concat()
+    }
+
+    fn message(entries: usize, chunk: Option<&str>) -> Message {
+        Message {
+            tag: "app".into(),
+            entries: vec![(time(), json!({"a": "b"})); entries],
+            chunk: chunk.map(|c| c.into()),
+        }
+    }
+
+    #[test]
+    fn test_decode_message() {
+        // Message mode, with and without an option.
+        let bytes = [&[0x93][..], &tag(), &TIME, &RECORD].concat();
+        assert_eq!(decode_message(&bytes), Ok(message(1, None)));
+        let bytes = [&[0x94][..], &tag(), &TIME, &RECORD, &chunk_option()].concat();
+        assert_eq!(decode_message(&bytes), Ok(message(1, Some("id"))));
+
+        // Integer times.
+        let bytes = [
+            &[0x93][..],
+            &tag(),
+            &[0xce, 0x5f, 0x5e, 0x10, 0x00],
+            &RECORD,
+        ]
+        .concat();
+        assert_eq!(
+            decode_message(&bytes).unwrap().entries[0].0,
+            Utc.timestamp(1600000000, 0)
+        );
+
+        // Forward mode.
+        let bytes = [
+            &[0x93][..],
+            &tag(),
+            &[0x92],
+            &entry(),
+            &entry(),
+            &chunk_option(),
+        ]
+        .concat();
+        assert_eq!(decode_message(&bytes), Ok(message(2, Some("id"))));
+
+        // Packed forward mode.
+        let packed = [entry(), entry(), entry()].concat();
+        let bytes = [&[0x92][..], &tag(), &[0xc4, packed.len() as u8], &packed].concat();
+        assert_eq!(decode_message(&bytes), Ok(message(3, None)));
+
+        // Compressed packed forward mode.
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&packed).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let option = [&[0x81, 0xaa][..], b"compressed", &[0xa4], b"gzip"].concat();
+        let bytes = [
+            &[0x93][..],
+            &tag(),
+            &[0xc4, compressed.len() as u8],
+            &compressed,
+            &option,
+        ]
+        .concat();
+        assert_eq!(decode_message(&bytes), Ok(message(3, None)));
+    }
+
+    #[test]
+    fn test_decode_message_errors() {
+        // Not an array.
+        assert!(decode_message(&RECORD).is_err());
+        // A record that isn't a map.
+        assert!(decode_message(&[&[0x93][..], &tag(), &TIME, &[0x01]].concat()).is_err());
+        // An unknown time extension.
+        let time = [0xd7, 0x01, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(decode_message(&[&[0x93][..], &tag(), &time, &RECORD].concat()).is_err());
+        // Too many elements.
+        let bytes = [
+            &[0x95][..],
+            &tag(),
+            &TIME,
+            &RECORD,
+            &chunk_option(),
+            &[0xc0],
+        ]
+        .concat();
+        assert!(decode_message(&bytes).is_err());
+        // Packed events with a trailing partial event.
+        let packed = [&entry()[..], &[0x92]].concat();
+        let bytes = [&[0x92][..], &tag(), &[0xc4, packed.len() as u8], &packed].concat();
+        assert!(decode_message(&bytes).is_err());
+        // Packed events that decompress to more than the maximum length.
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&vec![0; MAX_MESSAGE_LEN + 1]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let option = [&[0x81, 0xaa][..], b"compressed", &[0xa4], b"gzip"].concat();
+        let bytes = [
+            &[0x93][..],
+            &tag(),
+            &[0xc6],
+            &(compressed.len() as u32).to_be_bytes(),
+            &compressed,
+            &option,
+        ]
+        .concat();
+        assert!(decode_message(&bytes).unwrap_err().contains("exceed"));
+    }
+
+    #[test]
+    fn test_message_scanner() {
+        let message = [&[0x93][..], &tag(), &TIME, &RECORD].concat();
+        let input = [&message[..], &message].concat();
+
+        let mut scanner = MessageScanner::default();
+        for end in 0..message.len() {
+            assert_eq!(scanner.scan(&input[..end]), Ok(None));
+        }
+        assert_eq!(scanner.scan(&input), Ok(Some(message.len())));
+        assert_eq!(
+            scanner.scan(&input[message.len()..]),
+            Ok(Some(message.len()))
+        );
+
+        // Lengths are checked before the data arrives.
+        let mut scanner = MessageScanner::default();
+        assert!(scanner.scan(&[0xc6, 0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(MessageScanner::default().scan(&[0xc1]).is_err());
+    }
+
+    #[test]
+    fn test_encode_ack() {
+        assert_eq!(
+            encode_ack("id"),
+            vec![0x81, 0xa3, b'a', b'c', b'k', 0xa2, b'i', b'd']
+        );
+        let chunk = "x".repeat(40);
+        assert_eq!(&encode_ack(&chunk)[5..7], &[0xd9, 40]);
+    }
+}
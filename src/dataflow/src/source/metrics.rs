@@ -85,6 +85,7 @@ impl KinesisMetrics {
 pub(super) struct SourceSpecificMetrics {
     pub(super) operator_scheduled_counter: IntCounterVec,
     pub(super) capability: UIntGaugeVec,
+    pub(super) rows_ingested: UIntCounterVec,
 }
 
 impl SourceSpecificMetrics {
@@ -100,6 +101,11 @@ impl SourceSpecificMetrics {
                 help: "The current capability for this dataflow. This corresponds to min(mz_partition_closed_ts)",
                 var_labels: ["topic", "source_id", "worker_id"],
             )),
+            rows_ingested: registry.register(metric!(
+                name: "mz_source_rows_ingested_total",
+                help: "The number of rows or messages ingested by this source.",
+                var_labels: ["topic", "source_id", "worker_id"],
+            )),
         }
     }
 }
@@ -19,7 +19,7 @@ use mz_repr::MessagePayload;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::convert::TryInto;
 use std::fmt::{self, Debug};
 use std::rc::Rc;
@@ -44,7 +44,7 @@ use mz_ore::metrics::{CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, Gau
 use mz_ore::now::NowFn;
 use mz_ore::task;
 use prometheus::core::{AtomicI64, AtomicU64};
-use tracing::{debug, error, trace};
+use tracing::{debug, error, field, info_span, trace, Instrument};
 
 use mz_repr::{Diff, Row, Timestamp};
 use timely::dataflow::channels::pushers::Tee;
@@ -53,7 +53,7 @@ use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 use timely::scheduling::activate::{Activator, SyncActivator};
 use timely::Data;
-use tokio::sync::{mpsc, RwLock, RwLockReadGuard};
+use tokio::sync::{mpsc, watch, RwLock, RwLockReadGuard};
 
 use self::metrics::SourceBaseMetrics;
 
@@ -65,13 +65,18 @@ use crate::source::timestamp::TimestampBindingUpdater;
 use crate::source::timestamp::{AssignedTimestamp, SourceTimestamp};
 
 mod file;
+mod fluent;
 mod gen;
 mod kafka;
 mod kinesis;
 pub(super) mod metrics;
+mod otlp;
 mod postgres;
+mod prometheus;
 mod pubnub;
 mod s3;
+mod syslog;
+mod tempo;
 mod util;
 
 pub mod timestamp;
@@ -80,11 +85,16 @@ use differential_dataflow::Hashable;
 pub use file::read_file_task;
 pub use file::FileReadStyle;
 pub use file::FileSourceReader;
+pub use fluent::FluentSourceReader;
 pub use kafka::KafkaSourceReader;
 pub use kinesis::KinesisSourceReader;
+pub use otlp::OtlpSourceReader;
 pub use postgres::PostgresSourceReader;
+pub use prometheus::PrometheusSourceReader;
 pub use pubnub::PubNubSourceReader;
 pub use s3::S3SourceReader;
+pub use syslog::SyslogSourceReader;
+pub use tempo::TempoSourceReader;
 
 // Interval after which the source operator will yield control.
 const YIELD_INTERVAL: Duration = Duration::from_millis(10);
@@ -123,6 +133,8 @@ pub struct SourceConfig<'a, G> {
     pub base_metrics: &'a SourceBaseMetrics,
     /// An external ID to use for all AWS AssumeRole operations.
     pub aws_external_id: AwsExternalId,
+    /// Whether the source is paused by `ALTER SOURCE ... PAUSE`.
+    pub paused: watch::Receiver<bool>,
 }
 
 /// A record produced by a source
@@ -442,14 +454,24 @@ pub fn responsible_for(
     }
 }
 
+/// The number of most recent errors per source and worker that are kept in the
+/// `mz_source_errors` system table.
+const MAX_RECENT_ERRORS: usize = 10;
+
 /// Source-specific Prometheus metrics
 pub struct SourceMetrics {
     /// Number of times an operator gets scheduled
     operator_scheduled_counter: DeleteOnDropCounter<'static, AtomicI64, Vec<String>>,
     /// Value of the capability associated with this source
     capability: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
+    /// Number of rows, or messages for sources that are decoded later, ingested by this source
+    rows_ingested: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
+    /// The bytes and rows that are recorded in the system table, retracted on drop.
+    ingested: (i64, i64),
     /// Per-partition Prometheus metrics.
     pub partition_metrics: HashMap<PartitionId, PartitionMetrics>,
+    /// The most recent errors that are recorded in the system table, oldest first.
+    recent_errors: VecDeque<(Timestamp, SourceErrorDetails)>,
     logger: Option<Logger>,
     source_name: String,
     source_id: SourceInstanceId,
@@ -479,7 +501,13 @@ impl SourceMetrics {
                 .source_specific
                 .capability
                 .get_delete_on_drop_gauge(labels.to_vec()),
+            rows_ingested: base
+                .source_specific
+                .rows_ingested
+                .get_delete_on_drop_counter(labels.to_vec()),
+            ingested: (0, 0),
             partition_metrics: Default::default(),
+            recent_errors: Default::default(),
             logger,
             source_name: source_name.to_string(),
             source_id,
@@ -521,12 +549,78 @@ impl SourceMetrics {
             );
         }
     }
+
+    /// Record that the source ingested `bytes` bytes in `rows` rows, in both Prometheus and the
+    /// `mz_source_ingestion` system table. The bytes count towards `mz_bytes_read_total`.
+    pub fn record_ingested(&mut self, bytes: usize, rows: usize) {
+        if bytes == 0 && rows == 0 {
+            return;
+        }
+        self.base_metrics.bytes_read.inc_by(bytes as u64);
+        self.rows_ingested.inc_by(rows as u64);
+
+        let logger = match self.logger.as_mut() {
+            Some(logger) => logger,
+            None => return,
+        };
+        let (bytes, rows) = (bytes as i64, rows as i64);
+        logger.log(MaterializedEvent::SourceIngestion {
+            source_id: self.source_id,
+            bytes,
+            rows,
+        });
+        self.ingested.0 += bytes;
+        self.ingested.1 += rows;
+    }
+
+    /// Log an error emitted by the source at `timestamp`, retracting the oldest logged error if
+    /// there are more than [`MAX_RECENT_ERRORS`].
+    pub fn record_error(&mut self, timestamp: Timestamp, error: &SourceErrorDetails) {
+        let logger = match self.logger.as_mut() {
+            Some(logger) => logger,
+            None => return,
+        };
+
+        logger.log(MaterializedEvent::SourceError {
+            source_id: self.source_id,
+            timestamp,
+            error: error.clone(),
+            is_recent: true,
+        });
+        self.recent_errors.push_back((timestamp, error.clone()));
+
+        if self.recent_errors.len() > MAX_RECENT_ERRORS {
+            let (timestamp, error) = self.recent_errors.pop_front().unwrap();
+            logger.log(MaterializedEvent::SourceError {
+                source_id: self.source_id,
+                timestamp,
+                error,
+                is_recent: false,
+            });
+        }
+    }
 }
 
 impl Drop for SourceMetrics {
     fn drop(&mut self) {
-        // retract our partition from logging
+        // retract our partition and errors from logging
         if let Some(logger) = self.logger.as_mut() {
+            let (bytes, rows) = self.ingested;
+            if bytes != 0 || rows != 0 {
+                logger.log(MaterializedEvent::SourceIngestion {
+                    source_id: self.source_id,
+                    bytes: -bytes,
+                    rows: -rows,
+                });
+            }
+            for (timestamp, error) in self.recent_errors.drain(..) {
+                logger.log(MaterializedEvent::SourceError {
+                    source_id: self.source_id,
+                    timestamp,
+                    error,
+                    is_recent: false,
+                });
+            }
             for (partition, metric) in self.partition_metrics.iter() {
                 logger.log(MaterializedEvent::SourceInfo {
                     source_name: self.source_name.clone(),
@@ -617,8 +711,17 @@ enum MessageProcessing {
     YieldedWithDelay,
 }
 
+/// Messages are sent in batches so that sources emitting many rows per transaction only pay for
+/// one channel send per batch.
 type EventSender =
-    mpsc::Sender<Event<Option<Timestamp>, Result<(Row, Timestamp, Diff), SourceError>>>;
+    mpsc::Sender<Event<Option<Timestamp>, Vec<Result<(Row, Timestamp, Diff), SourceError>>>>;
+
+/// The number of batches that can be buffered between a simple source and its dataflow operator.
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// The maximum number of rows in a batch, which together with [`EVENT_CHANNEL_CAPACITY`] bounds
+/// the number of rows buffered for a simple source.
+const MAX_BATCH_ROWS: usize = 1024;
 
 /// An active transaction at a particular point in time. An instance of this struct is provided to
 /// a source when calling start_tx() on its timestamper. This has the effect of freezing the
@@ -634,18 +737,56 @@ pub struct SourceTransaction<'a> {
 impl SourceTransaction<'_> {
     /// Record an insertion of a row in the current transaction
     pub async fn insert(&self, row: Row) -> anyhow::Result<()> {
-        let msg = Ok((row, *self.timestamp, 1));
-        self.sender
-            .send(Event::Message(*self.timestamp, msg))
-            .await
-            .or_else(|_| Err(anyhow!("channel closed")))
+        self.send(vec![Ok((row, *self.timestamp, 1))]).await
     }
 
     /// Record a deletion of a row in the current transaction
     pub async fn delete(&self, row: Row) -> anyhow::Result<()> {
-        let msg = Ok((row, *self.timestamp, -1));
+        self.send(vec![Ok((row, *self.timestamp, -1))]).await
+    }
+
+    /// Record an insertion of each of the given rows in the current transaction. The rows are sent
+    /// to the dataflow in batches of at most [`MAX_BATCH_ROWS`] rows.
+    pub async fn insert_batch<I>(&self, rows: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = Row>,
+    {
+        self.send_batch(rows, 1).await
+    }
+
+    /// Record a deletion of each of the given rows in the current transaction. The rows are sent
+    /// to the dataflow in batches of at most [`MAX_BATCH_ROWS`] rows.
+    pub async fn delete_batch<I>(&self, rows: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = Row>,
+    {
+        self.send_batch(rows, -1).await
+    }
+
+    async fn send_batch<I>(&self, rows: I, diff: Diff) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = Row>,
+    {
+        let timestamp = *self.timestamp;
+        let mut msgs = vec![];
+        for row in rows {
+            msgs.push(Ok((row, timestamp, diff)));
+            if msgs.len() == MAX_BATCH_ROWS {
+                self.send(std::mem::take(&mut msgs)).await?;
+            }
+        }
+        if msgs.is_empty() {
+            return Ok(());
+        }
+        self.send(msgs).await
+    }
+
+    async fn send(
+        &self,
+        msgs: Vec<Result<(Row, Timestamp, Diff), SourceError>>,
+    ) -> anyhow::Result<()> {
         self.sender
-            .send(Event::Message(*self.timestamp, msg))
+            .send(Event::Message(*self.timestamp, msgs))
             .await
             .or_else(|_| Err(anyhow!("channel closed")))
     }
@@ -659,33 +800,65 @@ impl SourceTransaction<'_> {
 /// When a transaction is started using [start_tx](Timestamper::start_tx) the internal clock will be
 /// frozen and any subsequent rows will be timestamped with the exact same timestamp. The
 /// transaction is committed automatically as soon as the transaction object gets dropped.
+///
+/// While the source is paused by `ALTER SOURCE ... PAUSE`, starting a transaction waits until it
+/// is resumed, while the clock keeps ticking. Sources that hold on to upstream resources, like
+/// connections, can also wait for [paused](Timestamper::paused) to release them.
 pub struct Timestamper {
     inner: Arc<RwLock<Timestamp>>,
     sender: EventSender,
     tick_duration: Duration,
     now: NowFn,
+    paused: watch::Receiver<bool>,
 }
 
 impl Timestamper {
-    fn new(sender: EventSender, tick_duration: Duration, now: NowFn) -> Self {
+    fn new(
+        sender: EventSender,
+        tick_duration: Duration,
+        now: NowFn,
+        paused: watch::Receiver<bool>,
+    ) -> Self {
         let ts = now();
         Self {
             inner: Arc::new(RwLock::new(ts)),
             sender,
             tick_duration,
             now,
+            paused,
         }
     }
 
     /// Start a transaction at a particular point in time. The timestamper will freeze its internal
     /// clock while a transaction is active.
     pub async fn start_tx<'a>(&'a self) -> SourceTransaction<'a> {
+        self.resumed().await;
         SourceTransaction {
             timestamp: self.inner.read().await,
             sender: &self.sender,
         }
     }
 
+    /// Waits until the source is paused.
+    pub async fn paused(&self) {
+        self.wait_for_pause(true).await
+    }
+
+    /// Waits until the source is not paused, which it isn't unless it was paused.
+    pub async fn resumed(&self) {
+        self.wait_for_pause(false).await
+    }
+
+    async fn wait_for_pause(&self, paused: bool) {
+        let mut rx = self.paused.clone();
+        while *rx.borrow() != paused {
+            if rx.changed().await.is_err() {
+                // The source is being dropped, which will drop this future too.
+                futures::future::pending::<()>().await;
+            }
+        }
+    }
+
     /// Record an insertion of a row
     pub async fn insert(&self, row: Row) -> anyhow::Result<()> {
         self.start_tx().await.insert(row).await
@@ -701,7 +874,7 @@ impl Timestamper {
     async fn error(&self, err: SourceError) -> anyhow::Result<()> {
         let timestamp = self.inner.read().await;
         self.sender
-            .send(Event::Message(*timestamp, Err(err)))
+            .send(Event::Message(*timestamp, vec![Err(err)]))
             .await
             .or_else(|_| Err(anyhow!("channel closed")))
     }
@@ -742,6 +915,9 @@ impl Timestamper {
 ///
 /// Each simple source is given access to a timestamper instance that can be used to insert or
 /// retract rows for this source. See the API of the [Timestamper](Timestamper) for more details.
+///
+/// Sources are started in a `simple_source` tracing span. They can record the address or URL they
+/// ingest from in its `endpoint` field.
 #[async_trait]
 pub trait SimpleSource {
     /// Consumes the instance of this SimpleSource and converts it into an async state machine that
@@ -778,16 +954,23 @@ where
         logger,
         now,
         base_metrics,
+        paused,
         ..
     } = config;
 
-    let (tx, mut rx) = mpsc::channel(64);
+    let (tx, mut rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
 
     if active {
+        let span = info_span!(
+            "simple_source",
+            source_id = %id.source_id,
+            name = %name,
+            endpoint = field::Empty,
+        );
         task::spawn(
             || format!("source_simple_timestamper:{}", id.source_id),
             async move {
-                let timestamper = Timestamper::new(tx, timestamp_frequency, now);
+                let timestamper = Timestamper::new(tx, timestamp_frequency, now, paused);
                 let source = connector.start(&timestamper);
                 tokio::pin!(source);
 
@@ -806,7 +989,8 @@ where
                         }
                     }
                 }
-            },
+            }
+            .instrument(span),
         );
     }
 
@@ -854,8 +1038,19 @@ where
                         metrics.record_partition_offsets(metric_updates);
                         cap.downgrade(&time);
                     }
-                    Some(Event::Message(time, data)) => {
-                        output.session(&cap.delayed(&time)).give(data);
+                    Some(Event::Message(time, mut data)) => {
+                        let (mut bytes, mut rows) = (0, 0);
+                        for r in &data {
+                            match r {
+                                Ok((row, _, _)) => {
+                                    bytes += row.data().len();
+                                    rows += 1;
+                                }
+                                Err(err) => metrics.record_error(time, &err.error),
+                            }
+                        }
+                        metrics.record_ingested(bytes, rows);
+                        output.session(&cap.delayed(&time)).give_vec(&mut data);
                     }
                     None => {
                         return SourceStatus::Done;
@@ -913,6 +1108,7 @@ where
         encoding,
         logger,
         base_metrics,
+        paused,
         ..
     } = config;
 
@@ -1053,8 +1249,6 @@ where
             None => (None, None, None, 0),
         };
 
-    let bytes_read_counter = base_metrics.bytes_read.clone();
-
     let should_emit_timestamp_bindings = source_persist.is_some();
 
     let (stream, ts_bindings_stream, capability) = source(scope, name.clone(), move |info| {
@@ -1168,7 +1362,8 @@ where
             // Bound execution of operator to prevent a single operator from hogging
             // the CPU if there are many messages to process
             let timer = Instant::now();
-            // Accumulate updates to bytes_read for Prometheus metrics collection
+            // Accumulate updates to bytes_read for Prometheus metrics collection and the
+            // `mz_source_ingestion` system table
             let mut bytes_read = 0;
             // Accumulate updates to offsets for system table metrics collection
             let mut metric_updates = HashMap::new();
@@ -1176,7 +1371,13 @@ where
             // Record operator has been scheduled
             source_metrics.operator_scheduled_counter.inc();
 
-            let mut source_state = (SourceStatus::Alive, MessageProcessing::Active);
+            // A paused source reads nothing, as if no new messages were available, but keeps
+            // closing timestamps.
+            let mut source_state = if *paused.borrow() {
+                (SourceStatus::Alive, MessageProcessing::YieldedWithDelay)
+            } else {
+                (SourceStatus::Alive, MessageProcessing::Active)
+            };
             while let (_, MessageProcessing::Active) = source_state {
                 source_state = match source_reader.get_next_message() {
                     Ok(NextMessage::Ready(message)) => {
@@ -1201,13 +1402,17 @@ where
                     }
                     Ok(NextMessage::Finished) => (SourceStatus::Done, MessageProcessing::Stopped),
                     Err(e) => {
-                        output.session(&cap).give(Err(e.to_string()));
+                        let e = e.to_string();
+                        source_metrics
+                            .record_error(*cap.time(), &SourceErrorDetails::FileIO(e.clone()));
+                        output.session(&cap).give(Err(e));
                         (SourceStatus::Done, MessageProcessing::Stopped)
                     }
                 };
             }
 
-            bytes_read_counter.inc_by(bytes_read as u64);
+            let messages_read: i64 = metric_updates.values().map(|(_, _, count)| count).sum();
+            source_metrics.record_ingested(bytes_read, messages_read as usize);
             source_metrics.record_partition_offsets(metric_updates);
 
             // Attempt to update the timestamp and finalize the currently pending bindings
@@ -0,0 +1,1055 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! OpenTelemetry sources.
+//!
+//! An OTLP source runs a server that accepts the telemetry that OpenTelemetry
+//! SDKs and collectors export with [OTLP/gRPC], i.e. calls to the `Export`
+//! method of the signal's service, or with [OTLP/HTTP], i.e. a protobuf
+//! `Export*ServiceRequest` per POST request to the path of the signal, like
+//! `/v1/logs`. Both are served on the same address. Each log record, span or
+//! metric data point becomes a row. Attributes, which can nest arbitrarily,
+//! become `jsonb` objects.
+//!
+//! [OTLP/gRPC]: https://opentelemetry.io/docs/reference/specification/protocol/otlp/#otlpgrpc
+//! [OTLP/HTTP]: https://opentelemetry.io/docs/reference/specification/protocol/otlp/#otlphttp
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::io::Read;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::read::GzDecoder;
+use futures::future;
+use futures::ready;
+use hyper::body::{HttpBody, SizeHint};
+use hyper::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use hyper::{Body, HeaderMap, Method, Request, Response, StatusCode};
+use prost::Message;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tonic::body::BoxBody;
+use tonic::Status;
+use tower_service::Service;
+
+use mz_dataflow_types::sources::encoding::OtlpSignal;
+use mz_dataflow_types::sources::OtlpSourceConnector;
+use mz_expr::SourceInstanceId;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::{Datum, Row, RowPacker};
+
+use crate::source::gen::opentelemetry::proto::collector::logs::v1::{
+    logs_service_server::{LogsService, LogsServiceServer},
+    ExportLogsServiceResponse,
+};
+use crate::source::gen::opentelemetry::proto::collector::metrics::v1::{
+    metrics_service_server::{MetricsService, MetricsServiceServer},
+    ExportMetricsServiceResponse,
+};
+use crate::source::gen::opentelemetry::proto::collector::trace::v1::{
+    trace_service_server::{TraceService, TraceServiceServer},
+    ExportTraceServiceResponse,
+};
+use crate::source::gen::otlp::{
+    any_value, metric, number_data_point, AnyValue, ExportLogsServiceRequest,
+    ExportMetricsServiceRequest, ExportTraceServiceRequest, HistogramDataPoint, KeyValue,
+    NumberDataPoint, Resource,
+};
+use crate::source::util::{insert_and_wait, read_body, serve_http, Batch};
+use crate::source::{SimpleSource, SourceError, Timestamper};
+
+/// The maximum length of an export request. This matches the default of the
+/// OpenTelemetry Collector's OTLP receiver.
+const MAX_BODY_LEN: usize = 20 * 1024 * 1024;
+
+/// The maximum length of a decompressed export request.
+const MAX_DECOMPRESSED_LEN: usize = 64 * 1024 * 1024;
+
+/// Information required to receive OpenTelemetry data
+pub struct OtlpSourceReader {
+    source_id: SourceInstanceId,
+    connector: OtlpSourceConnector,
+}
+
+impl OtlpSourceReader {
+    /// Constructs a new instance
+    pub fn new(source_id: SourceInstanceId, connector: OtlpSourceConnector) -> Self {
+        Self {
+            source_id,
+            connector,
+        }
+    }
+}
+
+#[async_trait]
+impl SimpleSource for OtlpSourceReader {
+    async fn start(self, timestamper: &Timestamper) -> Result<(), SourceError> {
+        let source_id = self.source_id;
+        let OtlpSourceConnector { addr, signal } = self.connector;
+        let name = format!("otlp {}", signal.name());
+        let handle = move |req, tx| handle_request(req, signal, tx);
+        serve_http(source_id, timestamper, &name, addr, handle).await
+    }
+}
+
+/// Handles an export request over gRPC or over HTTP, which are told apart by
+/// their content type.
+async fn handle_request(
+    req: Request<Body>,
+    signal: OtlpSignal,
+    tx: mpsc::Sender<Batch>,
+) -> Result<Response<BoxBody>, Infallible> {
+    let content_type = req.headers().get(CONTENT_TYPE).map(|v| v.as_bytes());
+    if content_type.map_or(false, |t| t.starts_with(b"application/grpc")) {
+        return Ok(handle_grpc(req, signal, tx).await);
+    }
+    let res = handle_export(req, signal, tx).await?;
+    Ok(res.map(|body| {
+        body.map_err(|e| Status::internal(e.to_string()))
+            .boxed_unsync()
+    }))
+}
+
+/// Handles an OTLP/gRPC export request with the `Export` method of the
+/// signal's service.
+async fn handle_grpc(
+    req: Request<Body>,
+    signal: OtlpSignal,
+    tx: mpsc::Sender<Batch>,
+) -> Response<BoxBody> {
+    // Decompressing messages is up to the service, which wouldn't limit their
+    // decompressed length.
+    let encoding = req.headers().get("grpc-encoding").map(|v| v.as_bytes());
+    if !matches!(encoding, None | Some(b"identity")) {
+        return Status::unimplemented("export requests must be uncompressed").to_http();
+    }
+    let req = req.map(|body| LimitedBody {
+        body,
+        remaining: MAX_BODY_LEN,
+    });
+    let receiver = GrpcReceiver { tx };
+    match signal {
+        OtlpSignal::Logs => call(LogsServiceServer::new(receiver), req).await,
+        OtlpSignal::Traces => call(TraceServiceServer::new(receiver), req).await,
+        OtlpSignal::Metrics => call(MetricsServiceServer::new(receiver), req).await,
+    }
+}
+
+/// Calls a gRPC service, which reports failures as statuses in its responses.
+async fn call<S>(mut service: S, req: Request<LimitedBody>) -> Response<BoxBody>
+where
+    S: Service<Request<LimitedBody>, Response = Response<BoxBody>>,
+    S::Error: fmt::Display,
+{
+    let res = match future::poll_fn(|cx| service.poll_ready(cx)).await {
+        Ok(()) => service.call(req).await,
+        Err(e) => Err(e),
+    };
+    res.unwrap_or_else(|e| Status::internal(e.to_string()).to_http())
+}
+
+/// A request body that fails once it is longer than a maximum, since gRPC
+/// services don't limit the length of the messages they decode.
+struct LimitedBody {
+    body: Body,
+    remaining: usize,
+}
+
+impl HttpBody for LimitedBody {
+    type Data = Bytes;
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = &mut *self;
+        let chunk = match ready!(Pin::new(&mut this.body).poll_data(cx)) {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+            None => return Poll::Ready(None),
+        };
+        if chunk.len() > this.remaining {
+            let e = format!("request body exceeds {} bytes", MAX_BODY_LEN);
+            return Poll::Ready(Some(Err(e.into())));
+        }
+        this.remaining -= chunk.len();
+        Poll::Ready(Some(Ok(chunk)))
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.body)
+            .poll_trailers(cx)
+            .map_err(Into::into)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+/// Implements the OTLP/gRPC services, whose requests are decoded by tonic
+/// rather than by [`decode_export`].
+struct GrpcReceiver {
+    tx: mpsc::Sender<Batch>,
+}
+
+impl GrpcReceiver {
+    async fn insert(&self, rows: Result<Vec<Row>, String>) -> Result<(), Status> {
+        // Like exporters over HTTP, exporters over gRPC only retry requests
+        // that fail with some statuses, like `UNAVAILABLE`.
+        let rows = rows.map_err(Status::invalid_argument)?;
+        if !insert_and_wait(&self.tx, rows).await {
+            return Err(Status::unavailable("source is shutting down"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LogsService for GrpcReceiver {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportLogsServiceRequest>,
+    ) -> Result<tonic::Response<ExportLogsServiceResponse>, Status> {
+        self.insert(logs_rows(request.get_ref())).await?;
+        Ok(tonic::Response::new(ExportLogsServiceResponse {}))
+    }
+}
+
+#[async_trait]
+impl TraceService for GrpcReceiver {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportTraceServiceRequest>,
+    ) -> Result<tonic::Response<ExportTraceServiceResponse>, Status> {
+        self.insert(traces_rows(request.get_ref())).await?;
+        Ok(tonic::Response::new(ExportTraceServiceResponse {}))
+    }
+}
+
+#[async_trait]
+impl MetricsService for GrpcReceiver {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportMetricsServiceRequest>,
+    ) -> Result<tonic::Response<ExportMetricsServiceResponse>, Status> {
+        self.insert(metrics_rows(request.get_ref())).await?;
+        Ok(tonic::Response::new(ExportMetricsServiceResponse {}))
+    }
+}
+
+async fn handle_export(
+    req: Request<Body>,
+    signal: OtlpSignal,
+    tx: mpsc::Sender<Batch>,
+) -> Result<Response<Body>, Infallible> {
+    fn respond(status: StatusCode, body: impl Into<Body>) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(body.into())
+            .expect("response is valid")
+    }
+
+    let path = format!("/v1/{}", signal.name());
+    if req.uri().path() != path {
+        return Ok(respond(
+            StatusCode::NOT_FOUND,
+            format!("this source only accepts {} at {}", signal.name(), path),
+        ));
+    }
+    if req.method() != Method::POST {
+        return Ok(respond(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "export requests must use POST",
+        ));
+    }
+    let content_type = req.headers().get(CONTENT_TYPE).map(|v| v.as_bytes());
+    if !matches!(content_type, Some(b"application/x-protobuf")) {
+        return Ok(respond(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "export requests must be application/x-protobuf",
+        ));
+    }
+    let gzip = match req.headers().get(CONTENT_ENCODING).map(|v| v.as_bytes()) {
+        None | Some(b"identity") => false,
+        Some(b"gzip") => true,
+        Some(_) => {
+            return Ok(respond(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "export requests must be uncompressed or gzip-compressed",
+            ))
+        }
+    };
+    // Exporters don't retry requests that fail with a 400 or a 413, which is
+    // what we want for requests that will never decode.
+    let body = match read_body(req.into_body(), MAX_BODY_LEN).await {
+        Ok(body) => body,
+        Err((status, e)) => return Ok(respond(status, e)),
+    };
+    let rows = match decode_export(signal, &body, gzip) {
+        Ok(rows) => rows,
+        Err((status, e)) => return Ok(respond(status, e)),
+    };
+    if !insert_and_wait(&tx, rows).await {
+        return Ok(respond(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "source is shutting down",
+        ));
+    }
+    // An empty body is an `Export*ServiceResponse` without a partial success,
+    // i.e. a complete success.
+    let response = Response::builder()
+        .header(CONTENT_TYPE, "application/x-protobuf")
+        .body(Body::empty())
+        .expect("response is valid");
+    Ok(response)
+}
+
+/// Decodes the body of an export request for `signal` into rows, or returns
+/// the status and message to reject it with.
+fn decode_export(
+    signal: OtlpSignal,
+    body: &[u8],
+    gzip: bool,
+) -> Result<Vec<Row>, (StatusCode, String)> {
+    let mut decompressed = vec![];
+    let body = if gzip {
+        // Reading one byte past the maximum tells a request that decompresses
+        // to exactly the maximum apart from a longer one.
+        GzDecoder::new(body)
+            .take(MAX_DECOMPRESSED_LEN as u64 + 1)
+            .read_to_end(&mut decompressed)
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!("invalid gzip data: {}", e)))?;
+        if decompressed.len() > MAX_DECOMPRESSED_LEN {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!(
+                    "decompressed request exceeds {} bytes",
+                    MAX_DECOMPRESSED_LEN
+                ),
+            ));
+        }
+        &decompressed
+    } else {
+        body
+    };
+    let rows = match signal {
+        OtlpSignal::Logs => decode_logs(body),
+        OtlpSignal::Traces => decode_traces(body),
+        OtlpSignal::Metrics => decode_metrics(body),
+    };
+    rows.map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+fn decode_logs(body: &[u8]) -> Result<Vec<Row>, String> {
+    let request = ExportLogsServiceRequest::decode(body)
+        .map_err(|e| format!("invalid logs export request: {}", e))?;
+    logs_rows(&request)
+}
+
+/// Converts an `ExportLogsServiceRequest` into one row per log record.
+fn logs_rows(request: &ExportLogsServiceRequest) -> Result<Vec<Row>, String> {
+    let mut rows = vec![];
+    let mut row_buf = Row::default();
+    for resource_logs in &request.resource_logs {
+        let resource_attributes = resource_attributes_json(resource_logs.resource.as_ref());
+        for scope_logs in &resource_logs.scope_logs {
+            let scope = scope_logs.scope.as_ref();
+            for record in &scope_logs.log_records {
+                let mut packer = row_buf.packer();
+                packer.push(timestamp_datum(record.time_unix_nano));
+                packer.push(timestamp_datum(record.observed_time_unix_nano));
+                packer.push(match record.severity_number {
+                    0 => Datum::Null,
+                    n => Datum::Int32(n),
+                });
+                packer.push(non_empty(&record.severity_text));
+                match record.body.as_ref().and_then(any_value_json) {
+                    Some(body) => pack_json(&mut packer, body)?,
+                    None => packer.push(Datum::Null),
+                }
+                pack_json(&mut packer, attributes_json(&record.attributes))?;
+                packer.push(non_empty(&hex(&record.trace_id)));
+                packer.push(non_empty(&hex(&record.span_id)));
+                pack_json(&mut packer, resource_attributes.clone())?;
+                packer.push(scope.map_or(Datum::Null, |s| non_empty(&s.name)));
+                packer.push(scope.map_or(Datum::Null, |s| non_empty(&s.version)));
+                rows.push(row_buf.clone());
+            }
+        }
+    }
+    Ok(rows)
+}
+
+/// Decodes an `ExportTraceServiceRequest` into one row per span.
+pub(super) fn decode_traces(body: &[u8]) -> Result<Vec<Row>, String> {
+    let request = ExportTraceServiceRequest::decode(body)
+        .map_err(|e| format!("invalid traces export request: {}", e))?;
+    traces_rows(&request)
+}
+
+/// Converts an `ExportTraceServiceRequest` into one row per span.
+fn traces_rows(request: &ExportTraceServiceRequest) -> Result<Vec<Row>, String> {
+    let mut rows = vec![];
+    let mut row_buf = Row::default();
+    for resource_spans in &request.resource_spans {
+        let resource_attributes = resource_attributes_json(resource_spans.resource.as_ref());
+        for scope_spans in &resource_spans.scope_spans {
+            let scope = scope_spans.scope.as_ref();
+            for span in &scope_spans.spans {
+                if span.trace_id.is_empty() || span.span_id.is_empty() {
+                    return Err(format!(
+                        "span {} is missing its trace or span ID",
+                        span.name
+                    ));
+                }
+                let status = span.status.as_ref();
+                let mut packer = row_buf.packer();
+                packer.push(Datum::String(&hex(&span.trace_id)));
+                packer.push(Datum::String(&hex(&span.span_id)));
+                packer.push(non_empty(&hex(&span.parent_span_id)));
+                packer.push(Datum::String(&span.name));
+                packer.push(Datum::from(match span.kind {
+                    1 => Some("internal"),
+                    2 => Some("server"),
+                    3 => Some("client"),
+                    4 => Some("producer"),
+                    5 => Some("consumer"),
+                    _ => None,
+                }));
+                packer.push(timestamp_datum(span.start_time_unix_nano));
+                packer.push(timestamp_datum(span.end_time_unix_nano));
+                pack_json(&mut packer, attributes_json(&span.attributes))?;
+                packer.push(Datum::from(match status.map(|s| s.code) {
+                    Some(1) => Some("ok"),
+                    Some(2) => Some("error"),
+                    _ => None,
+                }));
+                packer.push(status.map_or(Datum::Null, |s| non_empty(&s.message)));
+                pack_json(&mut packer, resource_attributes.clone())?;
+                packer.push(scope.map_or(Datum::Null, |s| non_empty(&s.name)));
+                packer.push(scope.map_or(Datum::Null, |s| non_empty(&s.version)));
+                rows.push(row_buf.clone());
+            }
+        }
+    }
+    Ok(rows)
+}
+
+/// A data point of a gauge, a sum or a histogram.
+#[derive(Clone, Copy)]
+enum DataPoint<'a> {
+    Number(&'a NumberDataPoint),
+    Histogram(&'a HistogramDataPoint),
+}
+
+fn decode_metrics(body: &[u8]) -> Result<Vec<Row>, String> {
+    let request = ExportMetricsServiceRequest::decode(body)
+        .map_err(|e| format!("invalid metrics export request: {}", e))?;
+    metrics_rows(&request)
+}
+
+/// Converts an `ExportMetricsServiceRequest` into one row per data point. The
+/// points of gauges and sums have a value, while those of histograms have a
+/// count, a sum and buckets instead.
+fn metrics_rows(request: &ExportMetricsServiceRequest) -> Result<Vec<Row>, String> {
+    fn count(count: u64) -> Result<Datum<'static>, String> {
+        i64::try_from(count)
+            .map(Datum::Int64)
+            .map_err(|_| format!("histogram count out of range: {}", count))
+    }
+
+    let mut rows = vec![];
+    let mut row_buf = Row::default();
+    for resource_metrics in &request.resource_metrics {
+        let resource_attributes = resource_attributes_json(resource_metrics.resource.as_ref());
+        for scope_metrics in &resource_metrics.scope_metrics {
+            let scope = scope_metrics.scope.as_ref();
+            for metric in &scope_metrics.metrics {
+                let (kind, temporality, is_monotonic, points): (_, _, _, Vec<_>) =
+                    match &metric.data {
+                        Some(metric::Data::Gauge(gauge)) => (
+                            "gauge",
+                            0,
+                            None,
+                            gauge.data_points.iter().map(DataPoint::Number).collect(),
+                        ),
+                        Some(metric::Data::Sum(sum)) => (
+                            "sum",
+                            sum.aggregation_temporality,
+                            Some(sum.is_monotonic),
+                            sum.data_points.iter().map(DataPoint::Number).collect(),
+                        ),
+                        Some(metric::Data::Histogram(histogram)) => (
+                            "histogram",
+                            histogram.aggregation_temporality,
+                            None,
+                            histogram
+                                .data_points
+                                .iter()
+                                .map(DataPoint::Histogram)
+                                .collect(),
+                        ),
+                        // Exponential histograms and summaries.
+                        None => continue,
+                    };
+                for point in points {
+                    let (attributes, start_time, time) = match point {
+                        DataPoint::Number(p) => {
+                            (&p.attributes, p.start_time_unix_nano, p.time_unix_nano)
+                        }
+                        DataPoint::Histogram(p) => {
+                            (&p.attributes, p.start_time_unix_nano, p.time_unix_nano)
+                        }
+                    };
+                    let mut packer = row_buf.packer();
+                    packer.push(Datum::String(&metric.name));
+                    packer.push(non_empty(&metric.description));
+                    packer.push(non_empty(&metric.unit));
+                    packer.push(Datum::String(kind));
+                    packer.push(Datum::from(match temporality {
+                        1 => Some("delta"),
+                        2 => Some("cumulative"),
+                        _ => None,
+                    }));
+                    packer.push(Datum::from(is_monotonic));
+                    packer.push(timestamp_datum(start_time));
+                    packer.push(timestamp_datum(time));
+                    pack_json(&mut packer, attributes_json(attributes))?;
+                    match point {
+                        DataPoint::Number(p) => {
+                            packer.push(match p.value {
+                                Some(number_data_point::Value::AsDouble(d)) => Datum::from(d),
+                                Some(number_data_point::Value::AsInt(i)) => Datum::from(i as f64),
+                                None => Datum::Null,
+                            });
+                            packer.extend([Datum::Null; 4]);
+                        }
+                        DataPoint::Histogram(p) => {
+                            packer.push(Datum::Null);
+                            packer.push(count(p.count)?);
+                            packer.push(Datum::from(p.sum));
+                            let bucket_counts = p
+                                .bucket_counts
+                                .iter()
+                                .map(|c| count(*c))
+                                .collect::<Result<Vec<_>, _>>()?;
+                            packer.push_list(bucket_counts);
+                            packer.push_list(p.explicit_bounds.iter().map(|b| Datum::from(*b)));
+                        }
+                    }
+                    pack_json(&mut packer, resource_attributes.clone())?;
+                    packer.push(scope.map_or(Datum::Null, |s| non_empty(&s.name)));
+                    packer.push(scope.map_or(Datum::Null, |s| non_empty(&s.version)));
+                    rows.push(row_buf.clone());
+                }
+            }
+        }
+    }
+    Ok(rows)
+}
+
+fn resource_attributes_json(resource: Option<&Resource>) -> Value {
+    attributes_json(resource.map_or(&[][..], |r| &r.attributes[..]))
+}
+
+/// Converts attributes to a JSON object. If a key appears more than once the
+/// last value wins.
+fn attributes_json(attributes: &[KeyValue]) -> Value {
+    let attributes = attributes.iter().map(|kv| {
+        let value = kv.value.as_ref().and_then(any_value_json);
+        (kv.key.clone(), value.unwrap_or(Value::Null))
+    });
+    Value::Object(attributes.collect())
+}
+
+/// Converts an `AnyValue` to JSON, or returns `None` if it is empty. Bytes
+/// become hex strings, as trace and span IDs do.
+fn any_value_json(value: &AnyValue) -> Option<Value> {
+    Some(match value.value.as_ref()? {
+        any_value::Value::StringValue(s) => Value::String(s.clone()),
+        any_value::Value::BoolValue(b) => Value::Bool(*b),
+        any_value::Value::IntValue(i) => Value::from(*i),
+        any_value::Value::DoubleValue(d) => Value::from(*d),
+        any_value::Value::ArrayValue(array) => Value::Array(
+            array
+                .values
+                .iter()
+                .map(|v| any_value_json(v).unwrap_or(Value::Null))
+                .collect(),
+        ),
+        any_value::Value::KvlistValue(kvlist) => attributes_json(&kvlist.values),
+        any_value::Value::BytesValue(bytes) => Value::String(hex(bytes)),
+    })
+}
+
+fn pack_json(packer: &mut RowPacker, value: Value) -> Result<(), String> {
+    JsonbPacker::new(packer)
+        .pack_serde_json(value)
+        .map_err(|e| format!("failed to pack JSON: {}", e))
+}
+
+/// Converts nanoseconds since the Unix epoch to a timestamp, where 0 means
+/// that the time is unknown. Materialize timestamps only have microsecond
+/// precision, so the nanoseconds are truncated.
+fn timestamp_datum(nanos: u64) -> Datum<'static> {
+    if nanos == 0 {
+        return Datum::Null;
+    }
+    let secs = (nanos / 1_000_000_000) as i64;
+    let micros = (nanos % 1_000_000_000 / 1_000) as u32;
+    let ts = NaiveDateTime::from_timestamp(secs, micros * 1_000);
+    Datum::TimestampTz(DateTime::from_utc(ts, Utc))
+}
+
+fn non_empty(s: &str) -> Datum {
+    if s.is_empty() {
+        Datum::Null
+    } else {
+        Datum::String(s)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(s, "{:02x}", b).expect("writing to a string cannot fail");
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use chrono::{TimeZone, Utc};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use hyper::StatusCode;
+    use prost::Message;
+    use serde_json::json;
+
+    use mz_dataflow_types::sources::encoding::OtlpSignal;
+    use mz_repr::adt::jsonb::JsonbPacker;
+    use mz_repr::{Datum, Row};
+
+    use super::{decode_export, MAX_DECOMPRESSED_LEN};
+    use crate::source::gen::otlp::{
+        any_value, metric, number_data_point, AnyValue, ArrayValue, ExportLogsServiceRequest,
+        ExportMetricsServiceRequest, ExportTraceServiceRequest, Gauge, Histogram,
+        HistogramDataPoint, InstrumentationScope, KeyValue, LogRecord, Metric, NumberDataPoint,
+        Resource, ResourceLogs, ResourceMetrics, ResourceSpans, ScopeLogs, ScopeMetrics,
+        ScopeSpans, Span, Status, Sum,
+    };
+
+    fn string(s: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(s.into())),
+        })
+    }
+
+    fn attribute(key: &str, value: Option<AnyValue>) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value,
+        }
+    }
+
+    fn logs_request() -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Some(Resource {
+                    attributes: vec![attribute("service.name", string("checkout"))],
+                }),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(InstrumentationScope {
+                        name: "checkout-logger".into(),
+                        version: "".into(),
+                    }),
+                    log_records: vec![
+                        LogRecord {
+                            time_unix_nano: 1_600_000_000_123_456_789,
+                            observed_time_unix_nano: 1_600_000_001_000_000_000,
+                            severity_number: 17,
+                            severity_text: "ERROR".into(),
+                            body: string("payment declined"),
+                            attributes: vec![
+                                attribute(
+                                    "http.status_code",
+                                    Some(AnyValue {
+                                        value: Some(any_value::Value::IntValue(402)),
+                                    }),
+                                ),
+                                attribute(
+                                    "tags",
+                                    Some(AnyValue {
+                                        value: Some(any_value::Value::ArrayValue(ArrayValue {
+                                            values: vec![
+                                                string("a").unwrap(),
+                                                AnyValue {
+                                                    value: Some(any_value::Value::BoolValue(true)),
+                                                },
+                                            ],
+                                        })),
+                                    }),
+                                ),
+                            ],
+                            trace_id: vec![0x5b, 0x8e, 0xff, 0xf7],
+                            span_id: vec![0x05, 0x1d],
+                        },
+                        LogRecord::default(),
+                    ],
+                }],
+            }],
+        }
+    }
+
+    fn row(
+        datums: &[Datum],
+        body: Option<serde_json::Value>,
+        attributes: serde_json::Value,
+    ) -> Row {
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.extend(&datums[..4]);
+        match body {
+            Some(body) => JsonbPacker::new(&mut packer).pack_serde_json(body).unwrap(),
+            None => packer.push(Datum::Null),
+        }
+        JsonbPacker::new(&mut packer)
+            .pack_serde_json(attributes)
+            .unwrap();
+        packer.extend(&datums[4..6]);
+        JsonbPacker::new(&mut packer)
+            .pack_serde_json(json!({"service.name": "checkout"}))
+            .unwrap();
+        packer.extend(&datums[6..]);
+        row
+    }
+
+    #[test]
+    fn test_decode_logs() {
+        let expected = vec![
+            row(
+                &[
+                    Datum::TimestampTz(Utc.timestamp(1_600_000_000, 123_456_000)),
+                    Datum::TimestampTz(Utc.timestamp(1_600_000_001, 0)),
+                    Datum::Int32(17),
+                    Datum::String("ERROR"),
+                    Datum::String("5b8efff7"),
+                    Datum::String("051d"),
+                    Datum::String("checkout-logger"),
+                    Datum::Null,
+                ],
+                Some(json!("payment declined")),
+                json!({"http.status_code": 402, "tags": ["a", true]}),
+            ),
+            row(
+                &[
+                    Datum::Null,
+                    Datum::Null,
+                    Datum::Null,
+                    Datum::Null,
+                    Datum::Null,
+                    Datum::Null,
+                    Datum::String("checkout-logger"),
+                    Datum::Null,
+                ],
+                None,
+                json!({}),
+            ),
+        ];
+
+        let body = logs_request().encode_to_vec();
+        assert_eq!(
+            decode_export(OtlpSignal::Logs, &body, false),
+            Ok(expected.clone())
+        );
+
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&body).unwrap();
+        let body = encoder.finish().unwrap();
+        assert_eq!(decode_export(OtlpSignal::Logs, &body, true), Ok(expected));
+    }
+
+    #[test]
+    fn test_decode_logs_errors() {
+        assert!(decode_export(OtlpSignal::Logs, b"\xff\xff", false).is_err());
+        assert!(decode_export(OtlpSignal::Logs, b"not gzip", true).is_err());
+
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder
+            .write_all(&vec![0; MAX_DECOMPRESSED_LEN + 1])
+            .unwrap();
+        let body = encoder.finish().unwrap();
+        assert_eq!(
+            decode_export(OtlpSignal::Logs, &body, true).map_err(|(status, _)| status),
+            Err(StatusCode::PAYLOAD_TOO_LARGE)
+        );
+    }
+
+    #[test]
+    fn test_decode_traces() {
+        let root = Span {
+            trace_id: vec![0x5b, 0x8e, 0xff, 0xf7],
+            span_id: vec![0x05, 0x1d],
+            parent_span_id: vec![],
+            name: "GET /checkout".into(),
+            kind: 2,
+            start_time_unix_nano: 1_600_000_000_000_000_000,
+            end_time_unix_nano: 1_600_000_000_250_000_000,
+            attributes: vec![attribute("http.method", string("GET"))],
+            status: Some(Status {
+                message: "payment declined".into(),
+                code: 2,
+            }),
+        };
+        let child = Span {
+            span_id: vec![0x06],
+            parent_span_id: vec![0x05, 0x1d],
+            name: "charge".into(),
+            kind: 0,
+            attributes: vec![],
+            status: None,
+            ..root.clone()
+        };
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(Resource {
+                    attributes: vec![attribute("service.name", string("checkout"))],
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans: vec![root, child],
+                }],
+            }],
+        };
+
+        let span_row = |span_id, parent, name, kind, attributes, status: [Datum; 2]| {
+            let mut row = Row::default();
+            let mut packer = row.packer();
+            packer.push(Datum::String("5b8efff7"));
+            packer.push(Datum::String(span_id));
+            packer.push(parent);
+            packer.push(Datum::String(name));
+            packer.push(kind);
+            packer.push(Datum::TimestampTz(Utc.timestamp(1_600_000_000, 0)));
+            packer.push(Datum::TimestampTz(
+                Utc.timestamp(1_600_000_000, 250_000_000),
+            ));
+            JsonbPacker::new(&mut packer)
+                .pack_serde_json(attributes)
+                .unwrap();
+            packer.extend(status);
+            JsonbPacker::new(&mut packer)
+                .pack_serde_json(json!({"service.name": "checkout"}))
+                .unwrap();
+            packer.extend([Datum::Null, Datum::Null]);
+            row
+        };
+        let expected = vec![
+            span_row(
+                "051d",
+                Datum::Null,
+                "GET /checkout",
+                Datum::String("server"),
+                json!({"http.method": "GET"}),
+                [Datum::String("error"), Datum::String("payment declined")],
+            ),
+            span_row(
+                "06",
+                Datum::String("051d"),
+                "charge",
+                Datum::Null,
+                json!({}),
+                [Datum::Null, Datum::Null],
+            ),
+        ];
+        assert_eq!(
+            decode_export(OtlpSignal::Traces, &request.encode_to_vec(), false),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_decode_traces_errors() {
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: None,
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans: vec![Span::default()],
+                }],
+            }],
+        };
+        assert!(decode_export(OtlpSignal::Traces, &request.encode_to_vec(), false).is_err());
+    }
+
+    /// Packs a metrics row. The histogram columns are
+    /// `(count, sum, bucket_counts, explicit_bounds)`.
+    fn metric_row(
+        head: [Datum; 8],
+        attributes: serde_json::Value,
+        value: Datum,
+        histogram: Option<(i64, f64, Vec<i64>, Vec<f64>)>,
+    ) -> Row {
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.extend(head);
+        JsonbPacker::new(&mut packer)
+            .pack_serde_json(attributes)
+            .unwrap();
+        packer.push(value);
+        match histogram {
+            Some((count, sum, bucket_counts, explicit_bounds)) => {
+                packer.push(Datum::Int64(count));
+                packer.push(Datum::from(sum));
+                packer.push_list(bucket_counts.into_iter().map(Datum::Int64));
+                packer.push_list(explicit_bounds.into_iter().map(Datum::from));
+            }
+            None => packer.extend([Datum::Null; 4]),
+        }
+        JsonbPacker::new(&mut packer)
+            .pack_serde_json(json!({}))
+            .unwrap();
+        packer.extend([Datum::String("meter"), Datum::String("1.0")]);
+        row
+    }
+
+    #[test]
+    fn test_decode_metrics() {
+        let number_point = |value| NumberDataPoint {
+            attributes: vec![attribute("host", string("a"))],
+            start_time_unix_nano: 0,
+            time_unix_nano: 1_600_000_000_000_000_000,
+            value: Some(value),
+        };
+        let metric = |name: &str, data| Metric {
+            name: name.into(),
+            description: "".into(),
+            unit: "1".into(),
+            data: Some(data),
+        };
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: None,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "meter".into(),
+                        version: "1.0".into(),
+                    }),
+                    metrics: vec![
+                        metric(
+                            "queue_length",
+                            metric::Data::Gauge(Gauge {
+                                data_points: vec![number_point(number_data_point::Value::AsInt(3))],
+                            }),
+                        ),
+                        metric(
+                            "requests",
+                            metric::Data::Sum(Sum {
+                                data_points: vec![number_point(
+                                    number_data_point::Value::AsDouble(1.5),
+                                )],
+                                aggregation_temporality: 2,
+                                is_monotonic: true,
+                            }),
+                        ),
+                        metric(
+                            "latency",
+                            metric::Data::Histogram(Histogram {
+                                data_points: vec![HistogramDataPoint {
+                                    attributes: vec![],
+                                    start_time_unix_nano: 1_599_999_999_000_000_000,
+                                    time_unix_nano: 1_600_000_000_000_000_000,
+                                    count: 3,
+                                    sum: 0.5,
+                                    bucket_counts: vec![1, 2],
+                                    explicit_bounds: vec![0.1],
+                                }],
+                                aggregation_temporality: 1,
+                            }),
+                        ),
+                        Metric {
+                            data: None,
+                            ..metric("skipped", metric::Data::Gauge(Gauge::default()))
+                        },
+                    ],
+                }],
+            }],
+        };
+
+        let time = Datum::TimestampTz(Utc.timestamp(1_600_000_000, 0));
+        let unit = Datum::String("1");
+        let expected = vec![
+            metric_row(
+                [
+                    Datum::String("queue_length"),
+                    Datum::Null,
+                    unit,
+                    Datum::String("gauge"),
+                    Datum::Null,
+                    Datum::Null,
+                    Datum::Null,
+                    time,
+                ],
+                json!({"host": "a"}),
+                Datum::from(3.0),
+                None,
+            ),
+            metric_row(
+                [
+                    Datum::String("requests"),
+                    Datum::Null,
+                    unit,
+                    Datum::String("sum"),
+                    Datum::String("cumulative"),
+                    Datum::True,
+                    Datum::Null,
+                    time,
+                ],
+                json!({"host": "a"}),
+                Datum::from(1.5),
+                None,
+            ),
+            metric_row(
+                [
+                    Datum::String("latency"),
+                    Datum::Null,
+                    unit,
+                    Datum::String("histogram"),
+                    Datum::String("delta"),
+                    Datum::Null,
+                    Datum::TimestampTz(Utc.timestamp(1_599_999_999, 0)),
+                    time,
+                ],
+                json!({}),
+                Datum::Null,
+                Some((3, 0.5, vec![1, 2], vec![0.1])),
+            ),
+        ];
+        assert_eq!(
+            decode_export(OtlpSignal::Metrics, &request.encode_to_vec(), false),
+            Ok(expected)
+        );
+    }
+}
@@ -456,12 +456,8 @@ impl PostgresSourceReader {
 
                             let tx = timestamper.start_tx().await;
 
-                            for row in deletes.drain(..) {
-                                try_fatal!(tx.delete(row).await);
-                            }
-                            for row in inserts.drain(..) {
-                                try_fatal!(tx.insert(row).await);
-                            }
+                            try_fatal!(tx.delete_batch(deletes.drain(..)).await);
+                            try_fatal!(tx.insert_batch(inserts.drain(..)).await);
                             self.metrics.lsn.set(self.lsn.into());
                         }
                         Relation(relation) => {
@@ -0,0 +1,561 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Prometheus sources.
+//!
+//! A remote write source runs an HTTP server that accepts the samples that
+//! Prometheus servers push with the [remote write protocol], i.e. a
+//! snappy-compressed protobuf `WriteRequest` per POST request. A scrape source
+//! instead polls a metrics endpoint, the way a Prometheus server would, and
+//! parses the [text exposition format]. Either way, each sample becomes a
+//! `(metric, labels, timestamp, value)` row.
+//!
+//! [remote write protocol]: https://prometheus.io/docs/concepts/remote_write_spec/
+//! [text exposition format]: https://prometheus.io/docs/instrumenting/exposition_formats/
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use prost::Message;
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+use tracing::{info_span, warn, Instrument};
+use url::Url;
+
+use mz_dataflow_types::sources::PrometheusSourceConnector;
+use mz_expr::SourceInstanceId;
+use mz_repr::{Datum, Row};
+
+use crate::source::gen::prometheus::WriteRequest;
+use crate::source::util::{
+    insert_and_wait, insert_rows, read_body, record_endpoint, serve_http, Batch,
+};
+use crate::source::{SimpleSource, SourceError, Timestamper};
+
+/// The maximum length of a remote write request. Prometheus sends a few
+/// thousand samples per request, which compress to far less than this.
+const MAX_BODY_LEN: usize = 16 * 1024 * 1024;
+
+/// The maximum length of a decompressed remote write request.
+const MAX_DECOMPRESSED_LEN: usize = 64 * 1024 * 1024;
+
+/// Information required to ingest samples from Prometheus
+pub struct PrometheusSourceReader {
+    source_id: SourceInstanceId,
+    connector: PrometheusSourceConnector,
+}
+
+impl PrometheusSourceReader {
+    /// Constructs a new instance
+    pub fn new(source_id: SourceInstanceId, connector: PrometheusSourceConnector) -> Self {
+        Self {
+            source_id,
+            connector,
+        }
+    }
+}
+
+#[async_trait]
+impl SimpleSource for PrometheusSourceReader {
+    async fn start(self, timestamper: &Timestamper) -> Result<(), SourceError> {
+        match self.connector {
+            PrometheusSourceConnector::RemoteWrite { addr } => {
+                let name = "prometheus remote write";
+                serve_http(self.source_id, timestamper, name, addr, handle_remote_write).await
+            }
+            PrometheusSourceConnector::Scrape { url, interval } => {
+                scrape(self.source_id, url, interval, timestamper).await
+            }
+        }
+    }
+}
+
+async fn handle_remote_write(
+    req: Request<Body>,
+    tx: mpsc::Sender<Batch>,
+) -> Result<Response<Body>, Infallible> {
+    fn respond(status: StatusCode, body: impl Into<Body>) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(body.into())
+            .expect("response is valid")
+    }
+
+    if req.method() != Method::POST {
+        return Ok(respond(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "remote write requests must use POST",
+        ));
+    }
+    // Prometheus doesn't retry requests that fail with a 4xx status, which
+    // is what we want for requests that will never decode.
+    let body = match read_body(req.into_body(), MAX_BODY_LEN).await {
+        Ok(body) => body,
+        Err((status, e)) => return Ok(respond(status, e)),
+    };
+    let body = match decompress(&body) {
+        Ok(body) => body,
+        Err((status, e)) => return Ok(respond(status, e)),
+    };
+    let rows = match decode_write_request(&body) {
+        Ok(rows) => rows,
+        Err(e) => return Ok(respond(StatusCode::BAD_REQUEST, e)),
+    };
+    // Prometheus retries requests that fail with a 5xx status.
+    if !insert_and_wait(&tx, rows).await {
+        return Ok(respond(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "source is shutting down",
+        ));
+    }
+    Ok(respond(StatusCode::NO_CONTENT, Body::empty()))
+}
+
+/// Decompresses the body of a remote write request, which uses snappy's raw
+/// format, unless it would exceed the maximum length.
+fn decompress(body: &[u8]) -> Result<Vec<u8>, (StatusCode, String)> {
+    let invalid = |e: snap::Error| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("invalid snappy data: {}", e),
+        )
+    };
+    // The decompressed length is declared at the start of the data.
+    if snap::raw::decompress_len(body).map_err(invalid)? > MAX_DECOMPRESSED_LEN {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "decompressed request exceeds {} bytes",
+                MAX_DECOMPRESSED_LEN
+            ),
+        ));
+    }
+    snap::raw::Decoder::new()
+        .decompress_vec(body)
+        .map_err(invalid)
+}
+
+/// Decodes a `WriteRequest` into one row per sample.
+fn decode_write_request(body: &[u8]) -> Result<Vec<Row>, String> {
+    let request =
+        WriteRequest::decode(body).map_err(|e| format!("invalid remote write request: {}", e))?;
+
+    let mut rows = vec![];
+    let mut row_buf = Row::default();
+    for series in &request.timeseries {
+        let mut metric = None;
+        let mut labels = BTreeMap::new();
+        for label in &series.labels {
+            if label.name == "__name__" {
+                metric = Some(label.value.as_str());
+            } else {
+                labels.insert(label.name.as_str(), label.value.as_str());
+            }
+        }
+        let metric = metric.ok_or("time series is missing the __name__ label")?;
+        for sample in &series.samples {
+            let timestamp = timestamp_from_millis(sample.timestamp)
+                .ok_or_else(|| format!("invalid sample timestamp: {}", sample.timestamp))?;
+            let labels = labels.iter().map(|(k, v)| (*k, *v));
+            rows.push(pack_sample(
+                &mut row_buf,
+                metric,
+                labels,
+                timestamp,
+                sample.value,
+            ));
+        }
+    }
+    Ok(rows)
+}
+
+async fn scrape(
+    source_id: SourceInstanceId,
+    url: Url,
+    interval: Duration,
+    timestamper: &Timestamper,
+) -> Result<(), SourceError> {
+    record_endpoint(&url);
+    let client = mz_http_proxy::reqwest::client();
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut row_buf = Row::default();
+
+    loop {
+        ticker.tick().await;
+        // Like Prometheus, stamp samples without a timestamp with the time the
+        // scrape started, and treat failed scrapes as transient.
+        let scraped_at = Utc::now();
+        let response = async {
+            client
+                .get(url.clone())
+                .header("Accept", "text/plain;version=0.0.4")
+                .timeout(interval)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await
+        };
+        let text = match response.instrument(info_span!("scrape")).await {
+            Ok(text) => text,
+            Err(e) => {
+                warn!(
+                    "prometheus source {} failed to scrape {}: {}",
+                    source_id, url, e
+                );
+                continue;
+            }
+        };
+        let samples = match parse_exposition(&text) {
+            Ok(samples) => samples,
+            Err(e) => {
+                warn!(
+                    "prometheus source {} failed to parse {}: {}",
+                    source_id, url, e
+                );
+                continue;
+            }
+        };
+
+        let rows = samples.iter().map(|sample| {
+            let labels = sample.labels.iter().map(|(k, v)| (*k, v.as_ref()));
+            let timestamp = sample.timestamp.unwrap_or(scraped_at);
+            pack_sample(&mut row_buf, sample.metric, labels, timestamp, sample.value)
+        });
+        insert_rows(source_id, timestamper, rows.collect()).await?;
+    }
+}
+
+/// A sample in the text exposition format, e.g.
+/// `http_requests_total{method="post",code="200"} 1027 1395066363000`.
+#[derive(Debug, PartialEq)]
+struct ExpositionSample<'a> {
+    metric: &'a str,
+    labels: BTreeMap<&'a str, Cow<'a, str>>,
+    value: f64,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+/// Parses the samples in a scraped page, skipping comments, including the
+/// `# HELP` and `# TYPE` lines. As for Prometheus, one malformed line fails the
+/// whole page.
+fn parse_exposition(text: &str) -> Result<Vec<ExpositionSample>, String> {
+    let mut samples = vec![];
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let sample = parse_sample(line).map_err(|e| format!("{} in line: {}", e, line))?;
+        samples.push(sample);
+    }
+    Ok(samples)
+}
+
+fn parse_sample(line: &str) -> Result<ExpositionSample, String> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    let metric = &line[..name_end];
+    if metric.is_empty() {
+        return Err("missing metric name".into());
+    }
+
+    let mut rest = &line[name_end..];
+    let mut labels = BTreeMap::new();
+    if let Some(mut s) = rest.strip_prefix('{') {
+        loop {
+            s = s.trim_start();
+            if let Some(after) = s.strip_prefix('}') {
+                rest = after;
+                break;
+            }
+            let eq = s.find('=').ok_or("expected '=' after label name")?;
+            let name = s[..eq].trim();
+            let value = s[eq + 1..]
+                .trim_start()
+                .strip_prefix('"')
+                .ok_or("expected '\"' before label value")?;
+            let (value, after) = parse_label_value(value)?;
+            labels.insert(name, value);
+            s = after.trim_start();
+            if let Some(after) = s.strip_prefix(',') {
+                s = after;
+            } else if !s.starts_with('}') {
+                return Err("expected ',' or '}' after label value".into());
+            }
+        }
+    }
+
+    let mut fields = rest.split_ascii_whitespace();
+    let value = fields.next().ok_or("missing sample value")?;
+    // This accepts the `NaN`, `+Inf` and `-Inf` that Prometheus uses.
+    let value = value
+        .parse()
+        .map_err(|_| format!("invalid sample value {}", value))?;
+    let timestamp = match fields.next() {
+        None => None,
+        Some(ts) => Some(
+            ts.parse()
+                .ok()
+                .and_then(timestamp_from_millis)
+                .ok_or_else(|| format!("invalid sample timestamp {}", ts))?,
+        ),
+    };
+    if fields.next().is_some() {
+        return Err("unexpected data after sample timestamp".into());
+    }
+
+    Ok(ExpositionSample {
+        metric,
+        labels,
+        value,
+        timestamp,
+    })
+}
+
+/// Parses a quoted label value that starts after the opening quote, returning
+/// it along with what follows the closing quote. Only `\\`, `\"` and `\n` are
+/// escapes; other backslashes are taken literally.
+fn parse_label_value(s: &str) -> Result<(Cow<str>, &str), String> {
+    let mut escaped: Option<String> = None;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                let value = match escaped {
+                    Some(value) => Cow::Owned(value),
+                    None => Cow::Borrowed(&s[..i]),
+                };
+                return Ok((value, &s[i + 1..]));
+            }
+            '\\' => {
+                let value = escaped.get_or_insert_with(|| s[..i].to_string());
+                match chars.peek() {
+                    Some((_, '\\')) => value.push('\\'),
+                    Some((_, '"')) => value.push('"'),
+                    Some((_, 'n')) => value.push('\n'),
+                    _ => {
+                        value.push('\\');
+                        continue;
+                    }
+                }
+                chars.next();
+            }
+            c => {
+                if let Some(value) = escaped.as_mut() {
+                    value.push(c);
+                }
+            }
+        }
+    }
+    Err("unterminated label value".into())
+}
+
+/// Packs a sample into a `(metric, labels, timestamp, value)` row. The labels
+/// must be sorted by name.
+fn pack_sample<'a, I>(
+    row_buf: &mut Row,
+    metric: &str,
+    labels: I,
+    timestamp: DateTime<Utc>,
+    value: f64,
+) -> Row
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut packer = row_buf.packer();
+    packer.push(Datum::String(metric));
+    packer.push_dict(labels.into_iter().map(|(k, v)| (k, Datum::String(v))));
+    packer.push(Datum::TimestampTz(timestamp));
+    packer.push(Datum::from(value));
+    row_buf.clone()
+}
+
+fn timestamp_from_millis(millis: i64) -> Option<DateTime<Utc>> {
+    let secs = millis.div_euclid(1000);
+    let nanos = u32::try_from(millis.rem_euclid(1000) * 1_000_000).ok()?;
+    let ts = NaiveDateTime::from_timestamp_opt(secs, nanos)?;
+    Some(DateTime::from_utc(ts, Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+    use hyper::StatusCode;
+    use prost::Message;
+
+    use mz_repr::{Datum, Row};
+
+    use super::{decode_write_request, decompress, parse_exposition, ExpositionSample};
+    use crate::source::gen::prometheus::{Label, Sample, TimeSeries, WriteRequest};
+
+    fn label(name: &str, value: &str) -> Label {
+        Label {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    fn encode(request: WriteRequest) -> Vec<u8> {
+        request.encode_to_vec()
+    }
+
+    #[test]
+    fn test_decompress() {
+        let compressed = snap::raw::Encoder::new()
+            .compress_vec(b"remote write")
+            .unwrap();
+        assert_eq!(decompress(&compressed), Ok(b"remote write".to_vec()));
+
+        assert_eq!(
+            decompress(b"not snappy").map_err(|(status, _)| status),
+            Err(StatusCode::BAD_REQUEST)
+        );
+        // A header that declares 4 GiB of decompressed data.
+        assert_eq!(
+            decompress(b"\xff\xff\xff\xff\x0f").map_err(|(status, _)| status),
+            Err(StatusCode::PAYLOAD_TOO_LARGE)
+        );
+    }
+
+    #[test]
+    fn test_decode_write_request() {
+        let body = encode(WriteRequest {
+            timeseries: vec![TimeSeries {
+                labels: vec![
+                    label("job", "node"),
+                    label("__name__", "up"),
+                    label("instance", "localhost:9100"),
+                ],
+                samples: vec![
+                    Sample {
+                        value: 1.0,
+                        timestamp: 1_600_000_000_123,
+                    },
+                    Sample {
+                        value: 0.0,
+                        timestamp: 1_600_000_015_123,
+                    },
+                ],
+            }],
+        });
+
+        let expected: Vec<_> = [(1.0, 1_600_000_000), (0.0, 1_600_000_015)]
+            .iter()
+            .map(|(value, secs)| {
+                let ts = NaiveDateTime::from_timestamp(*secs, 123_000_000);
+                let mut row = Row::default();
+                let mut packer = row.packer();
+                packer.push(Datum::String("up"));
+                packer.push_dict([
+                    ("instance", Datum::String("localhost:9100")),
+                    ("job", Datum::String("node")),
+                ]);
+                packer.push(Datum::TimestampTz(DateTime::from_utc(ts, Utc)));
+                packer.push(Datum::from(*value));
+                row
+            })
+            .collect();
+        assert_eq!(decode_write_request(&body), Ok(expected));
+    }
+
+    #[test]
+    fn test_decode_write_request_errors() {
+        assert!(decode_write_request(b"\xff\xff").is_err());
+        let unnamed = encode(WriteRequest {
+            timeseries: vec![TimeSeries {
+                labels: vec![label("job", "node")],
+                samples: vec![],
+            }],
+        });
+        assert!(decode_write_request(&unnamed).is_err());
+    }
+
+    fn sample<'a>(
+        metric: &'a str,
+        labels: &[(&'a str, &'a str)],
+        value: f64,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> ExpositionSample<'a> {
+        ExpositionSample {
+            metric,
+            labels: BTreeMap::from_iter(labels.iter().map(|(k, v)| (*k, (*v).into()))),
+            value,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_parse_exposition() {
+        let text = r#"# HELP http_requests_total The total number of HTTP requests.
+# TYPE http_requests_total counter
+http_requests_total{method="post",code="200"} 1027 1395066363000
+http_requests_total{method="post",code="400",}    3 1395066363000
+
+msdos_file_access_time_seconds{path="C:\\DIR\\FILE.TXT",error="Cannot find file:\n\"FILE.TXT\""} 1.458255915e9
+metric_without_timestamp_and_labels 12.47
+something_weird{problem="division by zero"} +Inf -3982045
+"#;
+        let timestamp = Some(Utc.ymd(2014, 3, 17).and_hms(14, 26, 3));
+        assert_eq!(
+            parse_exposition(text),
+            Ok(vec![
+                sample(
+                    "http_requests_total",
+                    &[("method", "post"), ("code", "200")],
+                    1027.0,
+                    timestamp
+                ),
+                sample(
+                    "http_requests_total",
+                    &[("method", "post"), ("code", "400")],
+                    3.0,
+                    timestamp
+                ),
+                sample(
+                    "msdos_file_access_time_seconds",
+                    &[
+                        ("path", r"C:\DIR\FILE.TXT"),
+                        ("error", "Cannot find file:\n\"FILE.TXT\"")
+                    ],
+                    1.458255915e9,
+                    None
+                ),
+                sample("metric_without_timestamp_and_labels", &[], 12.47, None),
+                sample(
+                    "something_weird",
+                    &[("problem", "division by zero")],
+                    f64::INFINITY,
+                    Some(Utc.ymd(1969, 12, 31).and_hms_milli(22, 53, 37, 955))
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_exposition_errors() {
+        assert!(parse_exposition("up").is_err());
+        assert!(parse_exposition("up one").is_err());
+        assert!(parse_exposition("up 1 yesterday").is_err());
+        assert!(parse_exposition("up 1 1395066363000 extra").is_err());
+        assert!(parse_exposition("{job=\"node\"} 1").is_err());
+        assert!(parse_exposition("up{job=node} 1").is_err());
+        assert!(parse_exposition("up{job=\"node\" 1").is_err());
+        assert!(parse_exposition("up{job=\"node\" instance=\"a\"} 1").is_err());
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Syslog sources.
+//!
+//! A syslog source accepts messages on one address over both UDP, one message
+//! per datagram as in [RFC 5426], and TCP, where messages are framed as in
+//! [RFC 6587]: either prefixed with their length in octets or terminated by a
+//! newline. If the source is configured with a certificate, TCP connections
+//! must use TLS as in [RFC 5425]. Messages are decoded like those of
+//! `FORMAT SYSLOG`; messages that fail to decode are logged and dropped.
+//!
+//! [RFC 5425]: https://datatracker.ietf.org/doc/html/rfc5425
+//! [RFC 5426]: https://datatracker.ietf.org/doc/html/rfc5426
+//! [RFC 6587]: https://datatracker.ietf.org/doc/html/rfc6587
+
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use bytes::{Buf, BytesMut};
+use futures::stream::{FuturesUnordered, StreamExt};
+use openssl::ssl::{Ssl, SslAcceptor, SslContext, SslFiletype, SslMethod};
+use tokio::io::AsyncRead;
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::mpsc;
+use tokio_openssl::SslStream;
+use tokio_util::codec::{Decoder, FramedRead};
+use tracing::{info, info_span, warn, Instrument};
+
+use mz_dataflow_types::sources::{SyslogSourceConnector, SyslogTlsConfig};
+use mz_dataflow_types::SourceErrorDetails;
+use mz_expr::SourceInstanceId;
+use mz_repr::Row;
+
+use crate::decode::syslog::SyslogDecoderState;
+use crate::source::util::{ingest, record_endpoint, Batch};
+use crate::source::{SimpleSource, SourceError, Timestamper};
+
+/// The maximum length of a message. RFC 5425 asks receivers to support
+/// messages of at least 8192 octets.
+const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+/// The number of messages that can be queued up to be inserted.
+const QUEUE_LEN: usize = 1024;
+
+/// Information required to receive syslog messages
+pub struct SyslogSourceReader {
+    source_id: SourceInstanceId,
+    connector: SyslogSourceConnector,
+}
+
+impl SyslogSourceReader {
+    /// Constructs a new instance
+    pub fn new(source_id: SourceInstanceId, connector: SyslogSourceConnector) -> Self {
+        Self {
+            source_id,
+            connector,
+        }
+    }
+}
+
+#[async_trait]
+impl SimpleSource for SyslogSourceReader {
+    async fn start(self, timestamper: &Timestamper) -> Result<(), SourceError> {
+        let source_id = self.source_id;
+        let SyslogSourceConnector { addr, tls } = self.connector;
+        let init_error = |error| SourceError {
+            source_id,
+            error: SourceErrorDetails::Initialization(error),
+        };
+
+        record_endpoint(addr);
+        let tls = tls
+            .map(|tls| tls_context(&tls))
+            .transpose()
+            .map_err(|e| init_error(format!("invalid TLS configuration: {}", e)))?;
+        let udp = UdpSocket::bind(addr)
+            .await
+            .map_err(|e| init_error(format!("unable to listen on {}: {}", addr, e)))?;
+        let tcp = TcpListener::bind(addr)
+            .await
+            .map_err(|e| init_error(format!("unable to listen on {}: {}", addr, e)))?;
+        info!("syslog source {} listening on {}", source_id, addr);
+
+        let (tx, rx) = mpsc::channel(QUEUE_LEN);
+        ingest(
+            source_id,
+            timestamper,
+            rx,
+            receive(source_id, udp, tcp, tls, tx),
+        )
+        .await
+    }
+}
+
+/// Receives datagrams, and accepts connections and reads their messages, until
+/// the source is dropped.
+async fn receive(
+    source_id: SourceInstanceId,
+    udp: UdpSocket,
+    tcp: TcpListener,
+    tls: Option<SslContext>,
+    tx: mpsc::Sender<Batch>,
+) -> Infallible {
+    let mut connections = FuturesUnordered::new();
+    let mut datagram = vec![0; MAX_MESSAGE_LEN];
+    let mut decoder = SyslogDecoderState::default();
+    loop {
+        tokio::select! {
+            res = udp.recv_from(&mut datagram) => match res {
+                Ok((len, peer)) => {
+                    if let Some(row) = decode(&mut decoder, &datagram[..len], peer) {
+                        // Senders don't wait for syslog messages to be
+                        // acknowledged.
+                        let _ = tx.send((vec![row], None)).await;
+                    }
+                }
+                // Errors are reported for earlier datagrams that could not be
+                // delivered, and don't affect the socket.
+                Err(e) => warn!("syslog source {} failed to receive: {}", source_id, e),
+            },
+            res = tcp.accept() => match res {
+                Ok((stream, peer)) => {
+                    let span = info_span!("connection", %peer, tls = tls.is_some());
+                    let tls = tls.clone();
+                    let connection = handle_connection(stream, peer, tls, tx.clone());
+                    connections.push(connection.instrument(span));
+                }
+                Err(e) => warn!("syslog source {} failed to accept: {}", source_id, e),
+            },
+            Some((peer, res)) = connections.next() => {
+                if let Err(e) = res {
+                    warn!("syslog source {} closed connection from {}: {}", source_id, peer, e);
+                }
+            }
+        }
+    }
+}
+
+fn tls_context(tls: &SyslogTlsConfig) -> Result<SslContext, anyhow::Error> {
+    let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
+    builder
+        .set_certificate_chain_file(&tls.cert)
+        .map_err(|e| anyhow!("reading {}: {}", tls.cert.display(), e))?;
+    builder
+        .set_private_key_file(&tls.key, SslFiletype::PEM)
+        .map_err(|e| anyhow!("reading {}: {}", tls.key.display(), e))?;
+    Ok(builder.build().into_context())
+}
+
+/// Reads the messages of a TCP connection until it is closed, and returns the
+/// peer's address along with the reason for closing it, if it is an error.
+async fn handle_connection(
+    stream: TcpStream,
+    peer: SocketAddr,
+    tls: Option<SslContext>,
+    tx: mpsc::Sender<Batch>,
+) -> (SocketAddr, Result<(), anyhow::Error>) {
+    let res = match tls {
+        None => read_messages(stream, peer, tx).await,
+        Some(context) => match accept_tls(stream, &context).await {
+            Ok(stream) => read_messages(stream, peer, tx).await,
+            Err(e) => Err(e),
+        },
+    };
+    (peer, res)
+}
+
+async fn accept_tls(
+    stream: TcpStream,
+    context: &SslContext,
+) -> Result<SslStream<TcpStream>, anyhow::Error> {
+    let mut stream = SslStream::new(Ssl::new(context)?, stream)?;
+    Pin::new(&mut stream).accept().await?;
+    Ok(stream)
+}
+
+async fn read_messages<R>(
+    reader: R,
+    peer: SocketAddr,
+    tx: mpsc::Sender<Batch>,
+) -> Result<(), anyhow::Error>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut decoder = SyslogDecoderState::default();
+    let mut frames = FramedRead::new(reader, SyslogCodec);
+    while let Some(frame) = frames.next().await {
+        if let Some(row) = decode(&mut decoder, &frame?, peer) {
+            // The receiver is only dropped along with this connection.
+            let _ = tx.send((vec![row], None)).await;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a message, or logs why it can't be decoded.
+fn decode(decoder: &mut SyslogDecoderState, message: &[u8], peer: SocketAddr) -> Option<Row> {
+    let end = message
+        .iter()
+        .rposition(|b| !b"\r\n\0".contains(b))
+        .map_or(0, |i| i + 1);
+    match decoder.decode(&message[..end]) {
+        Ok(row) => row,
+        Err(e) => {
+            warn!("dropping malformed syslog message from {}: {}", peer, e);
+            None
+        }
+    }
+}
+
+/// Splits a TCP stream into syslog messages, which are either prefixed with
+/// their length and a space (octet counting), or terminated by a newline
+/// (non-transparent framing). Senders may mix the two.
+struct SyslogCodec;
+
+impl Decoder for SyslogCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        fn invalid(message: &str) -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        }
+
+        // Skip the line breaks between messages, which some senders add even
+        // when counting octets.
+        let start = src
+            .iter()
+            .position(|b| !b"\r\n\0".contains(b))
+            .unwrap_or(src.len());
+        src.advance(start);
+
+        match src.first() {
+            None => Ok(None),
+            Some(b'1'..=b'9') => {
+                let digits = src.iter().take_while(|b| b.is_ascii_digit()).count();
+                let len = std::str::from_utf8(&src[..digits])
+                    .expect("digits are valid UTF-8")
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|len| *len <= MAX_MESSAGE_LEN)
+                    .ok_or_else(|| invalid("syslog message too long"))?;
+                if digits == src.len() {
+                    return Ok(None);
+                }
+                if src[digits] != b' ' {
+                    return Err(invalid("invalid syslog message length"));
+                }
+                if src.len() < digits + 1 + len {
+                    src.reserve(digits + 1 + len - src.len());
+                    return Ok(None);
+                }
+                src.advance(digits + 1);
+                Ok(Some(src.split_to(len)))
+            }
+            Some(_) => match src.iter().position(|b| *b == b'\n') {
+                Some(end) => {
+                    let message = src.split_to(end);
+                    src.advance(1);
+                    Ok(Some(message))
+                }
+                None if src.len() > MAX_MESSAGE_LEN => Err(invalid("syslog message too long")),
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        match self.decode(src)? {
+            Some(message) => Ok(Some(message)),
+            None if src.is_empty() => Ok(None),
+            // Without octet counting, closing the connection also ends the
+            // last message.
+            None if !src[0].is_ascii_digit() => Ok(Some(src.split_to(src.len()))),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "truncated syslog message",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use tokio_util::codec::Decoder;
+
+    use super::SyslogCodec;
+
+    fn frames(input: &str) -> Result<Vec<String>, std::io::Error> {
+        let mut src = BytesMut::from(input);
+        let mut frames = vec![];
+        while let Some(frame) = SyslogCodec.decode_eof(&mut src)? {
+            frames.push(String::from_utf8(frame.to_vec()).unwrap());
+        }
+        Ok(frames)
+    }
+
+    #[test]
+    fn test_syslog_codec() {
+        assert_eq!(
+            frames("<34>1 - - - - - - one\n9 <34>1 two\n\n<34>1 - three\r\n<34>1 four").unwrap(),
+            vec![
+                "<34>1 - - - - - - one",
+                "<34>1 two",
+                "<34>1 - three\r",
+                "<34>1 four"
+            ],
+        );
+
+        let mut src = BytesMut::from("19 <34>1 split");
+        assert_eq!(SyslogCodec.decode(&mut src).unwrap(), None);
+        src.extend_from_slice(b" message");
+        assert_eq!(
+            SyslogCodec.decode(&mut src).unwrap(),
+            Some(BytesMut::from("<34>1 split message"))
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_syslog_codec_errors() {
+        assert!(frames("12x <34>1 bad").is_err());
+        assert!(frames("99999999 <34>1 huge").is_err());
+        assert!(frames("99999999").is_err());
+        assert!(frames("20 <34>1 truncated").is_err());
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Grafana Tempo sources.
+//!
+//! A Tempo source polls Tempo's [search API] for recent traces that match a
+//! set of tags, fetches each trace that it hasn't ingested yet, and turns its
+//! spans into the same rows as OTLP trace sources. Tempo serves traces as
+//! protobuf messages that are wire compatible with OTLP export requests.
+//!
+//! [search API]: https://grafana.com/docs/tempo/latest/api_docs/#search
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use tokio::time::MissedTickBehavior;
+use tracing::{info_span, warn, Instrument};
+use url::Url;
+
+use mz_dataflow_types::sources::TempoSourceConnector;
+use mz_expr::SourceInstanceId;
+use mz_repr::Row;
+
+use crate::source::otlp::decode_traces;
+use crate::source::util::{insert_rows, record_endpoint};
+use crate::source::{SimpleSource, SourceError, Timestamper};
+
+/// The maximum number of traces to request per search. Tempo returns only 20
+/// by default.
+const SEARCH_LIMIT: usize = 1000;
+
+/// Information required to poll Tempo for traces
+pub struct TempoSourceReader {
+    source_id: SourceInstanceId,
+    connector: TempoSourceConnector,
+}
+
+impl TempoSourceReader {
+    /// Constructs a new instance
+    pub fn new(source_id: SourceInstanceId, connector: TempoSourceConnector) -> Self {
+        Self {
+            source_id,
+            connector,
+        }
+    }
+}
+
+#[async_trait]
+impl SimpleSource for TempoSourceReader {
+    async fn start(self, timestamper: &Timestamper) -> Result<(), SourceError> {
+        let source_id = self.source_id;
+        let TempoSourceConnector {
+            url,
+            tags,
+            interval,
+        } = self.connector;
+        record_endpoint(&url);
+        let client = mz_http_proxy::reqwest::client();
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // Consecutive searches overlap by an interval, to catch traces that
+        // Tempo indexes late, so the IDs of ingested traces must be remembered
+        // until the traces have started before the search window.
+        let lookback = chrono::Duration::from_std(interval * 2).expect("interval is in range");
+        let mut ingested: HashMap<String, DateTime<Utc>> = HashMap::new();
+
+        loop {
+            ticker.tick().await;
+            let now = Utc::now();
+            let window_start = now - lookback;
+            ingested.retain(|_, found_at| *found_at >= window_start);
+
+            let response = search(&client, &url, &tags, window_start, now)
+                .instrument(info_span!("search", start = %window_start, end = %now));
+            let traces = match response.await {
+                Ok(traces) => traces,
+                Err(e) => {
+                    warn!("tempo source {} failed to search {}: {}", source_id, url, e);
+                    continue;
+                }
+            };
+            if traces.len() >= SEARCH_LIMIT {
+                warn!(
+                    "tempo source {} found more than {} traces in one search; some may be missed",
+                    source_id, SEARCH_LIMIT
+                );
+            }
+
+            for trace_id in traces {
+                if ingested.contains_key(&trace_id) {
+                    continue;
+                }
+                // Traces that fail to fetch are retried on the next tick, as
+                // long as they are still in the search window.
+                let fetch = fetch_trace(&client, &url, &trace_id)
+                    .instrument(info_span!("fetch_trace", %trace_id));
+                let rows = match fetch.await {
+                    Ok(rows) => rows,
+                    Err(e) => {
+                        warn!(
+                            "tempo source {} failed to fetch trace {}: {}",
+                            source_id, trace_id, e
+                        );
+                        continue;
+                    }
+                };
+                insert_rows(source_id, timestamper, rows).await?;
+                ingested.insert(trace_id, now);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    traces: Vec<TraceMetadata>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceMetadata {
+    #[serde(rename = "traceID")]
+    trace_id: String,
+    /// Nanoseconds since the Unix epoch, as a string.
+    start_time_unix_nano: String,
+}
+
+/// Returns the IDs of the traces that match `tags` and started between `start`
+/// and `end`.
+async fn search(
+    client: &Client,
+    url: &Url,
+    tags: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<String>, String> {
+    // Tempo's time range selects the traces that overlap it, so the traces
+    // that started earlier are filtered out afterwards.
+    let text = client
+        .get(api_url(url, &["api", "search"]))
+        .query(&[
+            ("tags", tags.to_string()),
+            ("start", start.timestamp().to_string()),
+            ("end", (end.timestamp() + 1).to_string()),
+            ("limit", SEARCH_LIMIT.to_string()),
+        ])
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+    parse_search_response(&text, start)
+}
+
+fn parse_search_response(text: &str, start: DateTime<Utc>) -> Result<Vec<String>, String> {
+    let response: SearchResponse =
+        serde_json::from_str(text).map_err(|e| format!("invalid search response: {}", e))?;
+    let mut traces = vec![];
+    for trace in response.traces {
+        let nanos: i64 = trace
+            .start_time_unix_nano
+            .parse()
+            .map_err(|_| format!("invalid trace start time: {}", trace.start_time_unix_nano))?;
+        let started_at = NaiveDateTime::from_timestamp(
+            nanos.div_euclid(1_000_000_000),
+            nanos.rem_euclid(1_000_000_000) as u32,
+        );
+        if DateTime::<Utc>::from_utc(started_at, Utc) >= start {
+            traces.push(trace.trace_id);
+        }
+    }
+    Ok(traces)
+}
+
+/// Fetches the trace with the given ID and decodes its spans into rows.
+async fn fetch_trace(client: &Client, url: &Url, trace_id: &str) -> Result<Vec<Row>, String> {
+    let body = client
+        .get(api_url(url, &["api", "traces", trace_id]))
+        .header("Accept", "application/protobuf")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+    decode_traces(&body)
+}
+
+/// Appends `segments` to the path of Tempo's base URL, which may itself have a
+/// path if Tempo is behind a proxy.
+fn api_url(url: &Url, segments: &[&str]) -> Url {
+    let mut url = url.clone();
+    url.path_segments_mut()
+        .expect("http URLs can be a base")
+        .pop_if_empty()
+        .extend(segments);
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use url::Url;
+
+    use super::{api_url, parse_search_response};
+
+    #[test]
+    fn test_parse_search_response() {
+        let text = r#"{
+            "traces": [
+                {
+                    "traceID": "2f3e0cee77ae5dc9c17ade3689eb2e54",
+                    "rootServiceName": "shop-backend",
+                    "rootTraceName": "update-billing",
+                    "startTimeUnixNano": "1600000000500000000",
+                    "durationMs": 557
+                },
+                {
+                    "traceID": "1b4a3c",
+                    "startTimeUnixNano": "1599999999000000000"
+                }
+            ],
+            "metrics": {"inspectedTraces": 2}
+        }"#;
+        let start = Utc.timestamp(1_600_000_000, 0);
+        assert_eq!(
+            parse_search_response(text, start),
+            Ok(vec!["2f3e0cee77ae5dc9c17ade3689eb2e54".to_string()])
+        );
+        assert_eq!(parse_search_response("{}", start), Ok(vec![]));
+        assert!(parse_search_response("[]", start).is_err());
+        assert!(parse_search_response(
+            r#"{"traces": [{"traceID": "1", "startTimeUnixNano": "soon"}]}"#,
+            start
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_api_url() {
+        let segments = &["api", "traces", "1b4a3c"];
+        for (base, expected) in [
+            ("http://tempo:3200", "http://tempo:3200/api/traces/1b4a3c"),
+            ("http://tempo:3200/", "http://tempo:3200/api/traces/1b4a3c"),
+            (
+                "https://grafana.example.com/tempo/",
+                "https://grafana.example.com/tempo/api/traces/1b4a3c",
+            ),
+        ] {
+            let url = Url::parse(base).unwrap();
+            assert_eq!(api_url(&url, segments).as_str(), expected);
+        }
+    }
+}
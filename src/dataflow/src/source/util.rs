@@ -8,18 +8,35 @@
 // by the Apache License, Version 2.0.
 
 use std::cell::RefCell;
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::net::SocketAddr;
 use std::rc::Rc;
 
+use bytes::{Bytes, BytesMut};
+use hyper::body::HttpBody;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, StatusCode};
 use timely::dataflow::channels::pushers::Tee;
 use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
 use timely::dataflow::operators::generic::{OperatorInfo, OutputHandle};
 use timely::dataflow::operators::{Capability, CapabilitySet};
 use timely::dataflow::{Scope, Stream};
 use timely::Data;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug_span, field, info, info_span, Instrument, Span};
 
-use mz_repr::Timestamp;
+use mz_dataflow_types::SourceErrorDetails;
+use mz_expr::SourceInstanceId;
+use mz_repr::{Row, Timestamp};
 
-use super::{SourceStatus, SourceToken};
+use super::{SourceError, SourceStatus, SourceToken, Timestamper};
+
+/// The maximum number of rows that [`ingest`] inserts in one transaction,
+/// unless a single batch has more.
+const MAX_INSERT_LEN: usize = 1024;
 
 /// Constructs a source named `name` in `scope` whose lifetime is controlled
 /// both internally and externally.
@@ -124,3 +141,173 @@ where
     // so we are guaranteed that `token` is non-None.
     (data_stream, secondary_stream, token.unwrap())
 }
+
+/// Rows that a push source has received, and the channel to acknowledge them
+/// on once they have been inserted, if their sender waits for that.
+pub type Batch = (Vec<Row>, Option<oneshot::Sender<()>>);
+
+/// Inserts the batches that a push source receives until receiving them
+/// fails.
+///
+/// Push sources receive data on tasks that can't borrow the timestamper, like
+/// the connections of a server, so those hand their rows over `rx` instead.
+/// Acknowledging batches once they have been inserted lets senders retry data
+/// that never made it into the source. `receive` is polled along with the
+/// inserts rather than spawned, so that it stops, and closes its connections,
+/// when the source is dropped. For the same reason it isn't polled while the
+/// source is paused, so that the source stops accepting data.
+pub async fn ingest<F, E>(
+    source_id: SourceInstanceId,
+    timestamper: &Timestamper,
+    mut rx: mpsc::Receiver<Batch>,
+    receive: F,
+) -> Result<(), SourceError>
+where
+    F: Future<Output = E>,
+    E: fmt::Display,
+{
+    tokio::pin!(receive);
+    loop {
+        timestamper.resumed().await;
+        tokio::select! {
+            _ = timestamper.paused() => {}
+            e = &mut receive => {
+                return Err(SourceError {
+                    source_id,
+                    error: SourceErrorDetails::FileIO(e.to_string()),
+                });
+            }
+            Some((mut rows, ack)) = rx.recv() => {
+                // Batches that queued up during the previous insert are
+                // inserted together.
+                let mut acks: Vec<_> = ack.into_iter().collect();
+                while rows.len() < MAX_INSERT_LEN {
+                    match rx.try_recv() {
+                        Ok((more, ack)) => {
+                            rows.extend(more);
+                            acks.extend(ack);
+                        }
+                        Err(_) => break,
+                    }
+                }
+                insert_rows(source_id, timestamper, rows).await?;
+                for ack in acks {
+                    let _ = ack.send(());
+                }
+            }
+        }
+    }
+}
+
+/// Inserts rows in one transaction.
+pub async fn insert_rows(
+    source_id: SourceInstanceId,
+    timestamper: &Timestamper,
+    rows: Vec<Row>,
+) -> Result<(), SourceError> {
+    let span = debug_span!("insert", rows = rows.len());
+    async { timestamper.start_tx().await.insert_batch(rows).await }
+        .instrument(span)
+        .await
+        .map_err(|e| SourceError {
+            source_id,
+            error: SourceErrorDetails::FileIO(e.to_string()),
+        })
+}
+
+/// Records the address or URL that the current source ingests from in the
+/// `endpoint` field of its span.
+pub fn record_endpoint<T: fmt::Display>(endpoint: T) {
+    Span::current().record("endpoint", &field::display(endpoint));
+}
+
+/// Hands rows over to [`ingest`] and waits until they have been inserted.
+/// Returns whether they were, which they are not if the source is shutting
+/// down.
+pub async fn insert_and_wait(tx: &mpsc::Sender<Batch>, rows: Vec<Row>) -> bool {
+    let (ack_tx, ack_rx) = oneshot::channel();
+    tx.send((rows, Some(ack_tx))).await.is_ok() && ack_rx.await.is_ok()
+}
+
+/// Serves the requests of a push source's HTTP server on `addr` with
+/// `handle`, which is given the channel to hand the rows it receives over to
+/// [`ingest`].
+pub async fn serve_http<H, R, B>(
+    source_id: SourceInstanceId,
+    timestamper: &Timestamper,
+    name: &str,
+    addr: SocketAddr,
+    handle: H,
+) -> Result<(), SourceError>
+where
+    H: Fn(Request<Body>, mpsc::Sender<Batch>) -> R + Clone + Send + 'static,
+    R: Future<Output = Result<Response<B>, Infallible>> + Send + 'static,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    record_endpoint(addr);
+    let (tx, rx) = mpsc::channel(16);
+    // Connections are served on their own tasks, so requests are given the
+    // source's span as their parent explicitly.
+    let source_span = Span::current();
+    let make_service = make_service_fn(move |_| {
+        let handle = handle.clone();
+        let tx = tx.clone();
+        let source_span = source_span.clone();
+        let service = service_fn(move |req: Request<Body>| {
+            let span = info_span!(
+                parent: &source_span,
+                "request",
+                method = %req.method(),
+                path = %req.uri().path(),
+            );
+            handle(req, tx.clone()).instrument(span)
+        });
+        async move { Ok::<_, Infallible>(service) }
+    });
+    let server = hyper::Server::try_bind(&addr)
+        .map_err(|e| SourceError {
+            source_id,
+            error: SourceErrorDetails::Initialization(format!(
+                "unable to listen on {}: {}",
+                addr, e
+            )),
+        })?
+        .serve(make_service);
+    info!("{} source {} listening on {}", name, source_id, addr);
+
+    let server = async move {
+        let e = server
+            .await
+            .err()
+            .map_or_else(|| "server stopped".into(), |e| e.to_string());
+        format!("{} server failed: {}", name, e)
+    };
+    ingest(source_id, timestamper, rx, server).await
+}
+
+/// Reads a request body of at most `max_len` bytes. Longer bodies are
+/// rejected with the status and message to respond with, before they are read
+/// if their length is known up front.
+pub async fn read_body(mut body: Body, max_len: usize) -> Result<Bytes, (StatusCode, String)> {
+    let too_large = || {
+        (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("request body exceeds {} bytes", max_len),
+        )
+    };
+    // The size hint is exact for requests with a Content-Length.
+    if body.size_hint().lower() > max_len as u64 {
+        return Err(too_large());
+    }
+    let mut buf = BytesMut::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+        if buf.len() + chunk.len() > max_len {
+            return Err(too_large());
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(buf.freeze())
+}
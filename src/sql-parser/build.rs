@@ -43,6 +43,16 @@ fn main() -> Result<()> {
             bail!("keywords list is not sorted: {:?} precedes {:?}", a, b);
         }
 
+        // A keyword's SQL spelling is its name in uppercase, unless the
+        // keywords file spells it out after the name.
+        let (keywords, sql_names): (Vec<_>, Vec<_>) = keywords
+            .iter()
+            .map(|line| match line.split_once(' ') {
+                Some((kw, name)) => (kw, name.to_string()),
+                None => (*line, line.to_uppercase()),
+            })
+            .unzip();
+
         let mut buf = CodegenBuf::new();
 
         buf.writeln("#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]");
@@ -55,24 +65,20 @@ fn main() -> Result<()> {
         buf.start_block("impl Keyword");
         buf.start_block("pub fn as_str(&self) -> &'static str");
         buf.start_block("match self");
-        for kw in &keywords {
-            buf.writeln(format!("Keyword::{} => {:?},", kw, kw.to_uppercase()));
+        for (kw, name) in keywords.iter().zip(&sql_names) {
+            buf.writeln(format!("Keyword::{} => {:?},", kw, name));
         }
         buf.end_block();
         buf.end_block();
         buf.end_block();
 
-        for kw in &keywords {
-            buf.writeln(format!(
-                "pub const {}: Keyword = Keyword::{};",
-                kw.to_uppercase(),
-                kw
-            ));
+        for (kw, name) in keywords.iter().zip(&sql_names) {
+            buf.writeln(format!("pub const {}: Keyword = Keyword::{};", name, kw));
         }
 
         let mut phf = phf_codegen::Map::new();
-        for kw in &keywords {
-            phf.entry(UncasedStr::new(kw), &format!("Keyword::{}", kw));
+        for (kw, name) in keywords.iter().zip(&sql_names) {
+            phf.entry(UncasedStr::new(name), &format!("Keyword::{}", kw));
         }
         buf.writeln(format!(
             "static KEYWORDS: phf::Map<&'static UncasedStr, Keyword> = {};",
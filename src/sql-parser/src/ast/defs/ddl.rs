@@ -282,12 +282,19 @@ pub enum Format<T: AstInfo> {
     Avro(AvroSchema<T>),
     Protobuf(ProtobufSchema<T>),
     Regex(String),
+    Grok(String),
     Csv {
         columns: CsvColumns,
         delimiter: char,
     },
     Json,
     Text,
+    Logfmt,
+    Syslog,
+    Cef,
+    AccessLog(AccessLogFormat),
+    Msgpack,
+    Cbor,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -317,6 +324,22 @@ impl AstDisplay for CsvColumns {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AccessLogFormat {
+    Common,
+    Combined,
+}
+
+impl AstDisplay for AccessLogFormat {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            AccessLogFormat::Common => f.write_str("COMMON"),
+            AccessLogFormat::Combined => f.write_str("COMBINED"),
+        }
+    }
+}
+impl_display!(AccessLogFormat);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SourceIncludeMetadataType {
     Key,
@@ -403,6 +426,11 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_node(&display::escape_single_quote_string(regex));
                 f.write_str("'");
             }
+            Self::Grok(pattern) => {
+                f.write_str("GROK '");
+                f.write_node(&display::escape_single_quote_string(pattern));
+                f.write_str("'");
+            }
             Self::Csv { columns, delimiter } => {
                 f.write_str("CSV WITH ");
                 f.write_node(columns);
@@ -415,6 +443,15 @@ impl<T: AstInfo> AstDisplay for Format<T> {
             }
             Self::Json => f.write_str("JSON"),
             Self::Text => f.write_str("TEXT"),
+            Self::Logfmt => f.write_str("LOGFMT"),
+            Self::Syslog => f.write_str("SYSLOG"),
+            Self::Cef => f.write_str("CEF"),
+            Self::AccessLog(format) => {
+                f.write_str("ACCESS_LOG ");
+                f.write_node(format);
+            }
+            Self::Msgpack => f.write_str("MSGPACK"),
+            Self::Cbor => f.write_str("CBOR"),
         }
     }
 }
@@ -496,6 +533,38 @@ pub enum CreateSourceConnector {
         /// The PubNub channel to subscribe to
         channel: String,
     },
+    PrometheusRemoteWrite {
+        /// The address to accept remote write requests on
+        addr: String,
+    },
+    PrometheusScrape {
+        /// The URL of the metrics endpoint to scrape
+        url: String,
+        /// How often to scrape the endpoint, as an interval string
+        interval: String,
+    },
+    Otlp {
+        /// The kind of telemetry to receive
+        signal: OtlpSignal,
+        /// The address to accept export requests on
+        addr: String,
+    },
+    Tempo {
+        /// The base URL of the Tempo API
+        url: String,
+        /// The tags to search for, in logfmt
+        tags: String,
+        /// How often to search for new traces, as an interval string
+        interval: String,
+    },
+    Syslog {
+        /// The address to accept messages on
+        addr: String,
+    },
+    Fluent {
+        /// The address to accept forward protocol connections on
+        addr: String,
+    },
 }
 
 impl AstDisplay for CreateSourceConnector {
@@ -577,11 +646,71 @@ impl AstDisplay for CreateSourceConnector {
                 f.write_str(&display::escape_single_quote_string(channel));
                 f.write_str("'");
             }
+            CreateSourceConnector::PrometheusRemoteWrite { addr } => {
+                f.write_str("PROMETHEUS REMOTE WRITE LISTEN '");
+                f.write_str(&display::escape_single_quote_string(addr));
+                f.write_str("'");
+            }
+            CreateSourceConnector::PrometheusScrape { url, interval } => {
+                f.write_str("PROMETHEUS SCRAPE '");
+                f.write_str(&display::escape_single_quote_string(url));
+                f.write_str("' TICK '");
+                f.write_str(&display::escape_single_quote_string(interval));
+                f.write_str("'");
+            }
+            CreateSourceConnector::Otlp { signal, addr } => {
+                f.write_str("OTLP ");
+                f.write_node(signal);
+                f.write_str(" LISTEN '");
+                f.write_str(&display::escape_single_quote_string(addr));
+                f.write_str("'");
+            }
+            CreateSourceConnector::Tempo {
+                url,
+                tags,
+                interval,
+            } => {
+                f.write_str("TEMPO '");
+                f.write_str(&display::escape_single_quote_string(url));
+                f.write_str("' SEARCH '");
+                f.write_str(&display::escape_single_quote_string(tags));
+                f.write_str("' TICK '");
+                f.write_str(&display::escape_single_quote_string(interval));
+                f.write_str("'");
+            }
+            CreateSourceConnector::Syslog { addr } => {
+                f.write_str("SYSLOG LISTEN '");
+                f.write_str(&display::escape_single_quote_string(addr));
+                f.write_str("'");
+            }
+            CreateSourceConnector::Fluent { addr } => {
+                f.write_str("FLUENT FORWARD LISTEN '");
+                f.write_str(&display::escape_single_quote_string(addr));
+                f.write_str("'");
+            }
         }
     }
 }
 impl_display!(CreateSourceConnector);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OtlpSignal {
+    Logs,
+    Traces,
+    Metrics,
+}
+
+impl AstDisplay for OtlpSignal {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OtlpSignal::Logs => f.write_str("LOGS"),
+            OtlpSignal::Traces => f.write_str("TRACES"),
+            OtlpSignal::Metrics => f.write_str("METRICS"),
+        }
+    }
+}
+impl_display!(OtlpSignal);
+
 impl<T: AstInfo> From<&CreateSinkConnector<T>> for ConnectorType {
     fn from(connector: &CreateSinkConnector<T>) -> ConnectorType {
         match connector {
@@ -48,6 +48,7 @@ pub enum Statement<T: AstInfo> {
     CreateRole(CreateRoleStatement),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterIndex(AlterIndexStatement),
+    AlterSource(AlterSourceStatement),
     Discard(DiscardStatement),
     DropDatabase(DropDatabaseStatement),
     DropObjects(DropObjectsStatement),
@@ -68,6 +69,7 @@ pub enum Statement<T: AstInfo> {
     Rollback(RollbackStatement),
     Tail(TailStatement<T>),
     Explain(ExplainStatement<T>),
+    ExplainSource(ExplainSourceStatement),
     Declare(DeclareStatement<T>),
     Fetch(FetchStatement),
     Close(CloseStatement),
@@ -97,6 +99,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateType(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
+            Statement::AlterSource(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropDatabase(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
@@ -117,6 +120,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Rollback(stmt) => f.write_node(stmt),
             Statement::Tail(stmt) => f.write_node(stmt),
             Statement::Explain(stmt) => f.write_node(stmt),
+            Statement::ExplainSource(stmt) => f.write_node(stmt),
             Statement::Declare(stmt) => f.write_node(stmt),
             Statement::Close(stmt) => f.write_node(stmt),
             Statement::Fetch(stmt) => f.write_node(stmt),
@@ -885,6 +889,38 @@ impl AstDisplay for AlterIndexStatement {
 
 impl_display!(AlterIndexStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterSourceAction {
+    Pause,
+    Resume,
+}
+
+/// `ALTER SOURCE ... {PAUSE | RESUME}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSourceStatement {
+    pub source_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterSourceAction,
+}
+
+impl AstDisplay for AlterSourceStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER SOURCE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.source_name);
+        f.write_str(" ");
+
+        match &self.action {
+            AlterSourceAction::Pause => f.write_str("PAUSE"),
+            AlterSourceAction::Resume => f.write_str("RESUME"),
+        }
+    }
+}
+
+impl_display!(AlterSourceStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -1342,6 +1378,20 @@ impl<T: AstInfo> AstDisplay for ExplainStatement<T> {
 }
 impl_display_t!(ExplainStatement);
 
+/// `EXPLAIN SOURCE <source>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplainSourceStatement {
+    pub source_name: UnresolvedObjectName,
+}
+
+impl AstDisplay for ExplainSourceStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("EXPLAIN SOURCE ");
+        f.write_node(&self.source_name);
+    }
+}
+impl_display!(ExplainSourceStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum InsertSource<T: AstInfo> {
     Query(Query<T>),
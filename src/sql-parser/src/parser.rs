@@ -1627,6 +1627,9 @@ impl<'a> Parser<'a> {
         } else if self.parse_keyword(REGEX) {
             let regex = self.parse_literal_string()?;
             Format::Regex(regex)
+        } else if self.parse_keyword(GROK) {
+            let pattern = self.parse_literal_string()?;
+            Format::Grok(pattern)
         } else if self.parse_keyword(CSV) {
             self.expect_keyword(WITH)?;
             let columns = if self.parse_keyword(HEADER) || self.parse_keyword(HEADERS) {
@@ -1654,10 +1657,28 @@ impl<'a> Parser<'a> {
             Format::Text
         } else if self.parse_keyword(BYTES) {
             Format::Bytes
+        } else if self.parse_keyword(LOGFMT) {
+            Format::Logfmt
+        } else if self.parse_keyword(SYSLOG) {
+            Format::Syslog
+        } else if self.parse_keyword(CEF) {
+            Format::Cef
+        } else if self.parse_keyword(ACCESS_LOG) {
+            let format = match self.expect_one_of_keywords(&[COMMON, COMBINED])? {
+                COMMON => AccessLogFormat::Common,
+                COMBINED => AccessLogFormat::Combined,
+                _ => unreachable!(),
+            };
+            Format::AccessLog(format)
+        } else if self.parse_keyword(MSGPACK) {
+            Format::Msgpack
+        } else if self.parse_keyword(CBOR) {
+            Format::Cbor
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, GROK, CSV, JSON, TEXT, BYTES, LOGFMT, SYSLOG, CEF, ACCESS_LOG, \
+                MSGPACK, or CBOR",
                 self.peek_token(),
             );
         };
@@ -2010,7 +2031,57 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_source_connector(&mut self) -> Result<CreateSourceConnector, ParserError> {
-        match self.expect_one_of_keywords(&[FILE, KAFKA, KINESIS, AVRO, S3, POSTGRES, PUBNUB])? {
+        match self.expect_one_of_keywords(&[
+            FILE, KAFKA, KINESIS, AVRO, S3, POSTGRES, PUBNUB, PROMETHEUS, OTLP, TEMPO, SYSLOG,
+            FLUENT,
+        ])? {
+            FLUENT => {
+                self.expect_keywords(&[FORWARD, LISTEN])?;
+                let addr = self.parse_literal_string()?;
+                Ok(CreateSourceConnector::Fluent { addr })
+            }
+            SYSLOG => {
+                self.expect_keyword(LISTEN)?;
+                let addr = self.parse_literal_string()?;
+                Ok(CreateSourceConnector::Syslog { addr })
+            }
+            TEMPO => {
+                let url = self.parse_literal_string()?;
+                self.expect_keyword(SEARCH)?;
+                let tags = self.parse_literal_string()?;
+                self.expect_keyword(TICK)?;
+                let interval = self.parse_literal_string()?;
+                Ok(CreateSourceConnector::Tempo {
+                    url,
+                    tags,
+                    interval,
+                })
+            }
+            OTLP => {
+                let signal = match self.expect_one_of_keywords(&[LOGS, TRACES, METRICS])? {
+                    LOGS => OtlpSignal::Logs,
+                    TRACES => OtlpSignal::Traces,
+                    METRICS => OtlpSignal::Metrics,
+                    _ => unreachable!(),
+                };
+                self.expect_keyword(LISTEN)?;
+                let addr = self.parse_literal_string()?;
+                Ok(CreateSourceConnector::Otlp { signal, addr })
+            }
+            PROMETHEUS => match self.expect_one_of_keywords(&[REMOTE, SCRAPE])? {
+                REMOTE => {
+                    self.expect_keywords(&[WRITE, LISTEN])?;
+                    let addr = self.parse_literal_string()?;
+                    Ok(CreateSourceConnector::PrometheusRemoteWrite { addr })
+                }
+                SCRAPE => {
+                    let url = self.parse_literal_string()?;
+                    self.expect_keyword(TICK)?;
+                    let interval = self.parse_literal_string()?;
+                    Ok(CreateSourceConnector::PrometheusScrape { url, interval })
+                }
+                _ => unreachable!(),
+            },
             PUBNUB => {
                 self.expect_keywords(&[SUBSCRIBE, KEY])?;
                 let subscribe_key = self.parse_literal_string()?;
@@ -2790,7 +2861,25 @@ impl<'a> Parser<'a> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        // We support `ALTER INDEX ... {RESET, SET} and `ALTER <object type> RENAME
+        // We support `ALTER INDEX ... {RESET, SET}`, `ALTER SOURCE ... {PAUSE, RESUME}`
+        // and `ALTER <object type> RENAME`
+        if object_type == ObjectType::Source {
+            let action = match self.parse_one_of_keywords(&[PAUSE, RESUME]) {
+                Some(PAUSE) => Some(AlterSourceAction::Pause),
+                Some(RESUME) => Some(AlterSourceAction::Resume),
+                Some(_) => unreachable!(),
+                None => None,
+            };
+
+            if let Some(action) = action {
+                return Ok(Statement::AlterSource(AlterSourceStatement {
+                    source_name: name,
+                    if_exists,
+                    action,
+                }));
+            }
+        }
+
         if object_type == ObjectType::Index {
             let action = match self.parse_one_of_keywords(&[RESET, SET]) {
                 Some(RESET) => {
@@ -4339,6 +4428,13 @@ impl<'a> Parser<'a> {
     /// Parse an `EXPLAIN` statement, assuming that the `EXPLAIN` token
     /// has already been consumed.
     fn parse_explain(&mut self) -> Result<Statement<Raw>, ParserError> {
+        // SOURCE source_name
+        if self.parse_keyword(SOURCE) {
+            return Ok(Statement::ExplainSource(ExplainSourceStatement {
+                source_name: self.parse_object_name()?,
+            }));
+        }
+
         // (TYPED)?
         let typed = self.parse_keyword(TYPED);
         let mut timing = false;
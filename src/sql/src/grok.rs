@@ -0,0 +1,276 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Expansion of [grok] patterns into regular expressions.
+//!
+//! A grok pattern is a regular expression that may additionally reference
+//! named patterns from a standard library with `%{PATTERN}`, or
+//! `%{PATTERN:field}` to capture the matched text into a column named `field`.
+//! Sources with `FORMAT GROK` are planned as `FORMAT REGEX` sources over the
+//! expanded expression, so each captured field becomes a nullable text column.
+//!
+//! The library follows the base patterns that ship with Logstash, adapted to
+//! the syntax of the [`regex`] crate: lookaround assertions and atomic groups
+//! are dropped, and all groups are non-capturing so that only named fields
+//! produce columns.
+//!
+//! [grok]: https://www.elastic.co/guide/en/logstash/current/plugins-filters-grok.html
+
+use anyhow::bail;
+
+/// The standard pattern library, as `(name, pattern)` pairs.
+const PATTERNS: &[(&str, &str)] = &[
+    ("USERNAME", r"[a-zA-Z0-9._-]+"),
+    ("USER", r"%{USERNAME}"),
+    ("EMAILLOCALPART", r"[a-zA-Z][a-zA-Z0-9_.+-=:]+"),
+    ("EMAILADDRESS", r"%{EMAILLOCALPART}@%{HOSTNAME}"),
+    ("INT", r"[+-]?[0-9]+"),
+    ("BASE10NUM", r"[+-]?(?:[0-9]+(?:\.[0-9]+)?|\.[0-9]+)"),
+    ("NUMBER", r"%{BASE10NUM}"),
+    ("BASE16NUM", r"[+-]?(?:0x)?[0-9A-Fa-f]+"),
+    ("POSINT", r"\b[1-9][0-9]*\b"),
+    ("NONNEGINT", r"\b[0-9]+\b"),
+    ("WORD", r"\b\w+\b"),
+    ("NOTSPACE", r"\S+"),
+    ("SPACE", r"\s*"),
+    ("DATA", r".*?"),
+    ("GREEDYDATA", r".*"),
+    (
+        "QUOTEDSTRING",
+        r#""(?:\\.|[^\\"])*"|'(?:\\.|[^\\'])*'|`(?:\\.|[^\\`])*`"#,
+    ),
+    ("QS", r"%{QUOTEDSTRING}"),
+    (
+        "UUID",
+        r"[A-Fa-f0-9]{8}-(?:[A-Fa-f0-9]{4}-){3}[A-Fa-f0-9]{12}",
+    ),
+    ("CISCOMAC", r"(?:[A-Fa-f0-9]{4}\.){2}[A-Fa-f0-9]{4}"),
+    ("WINDOWSMAC", r"(?:[A-Fa-f0-9]{2}-){5}[A-Fa-f0-9]{2}"),
+    ("COMMONMAC", r"(?:[A-Fa-f0-9]{2}:){5}[A-Fa-f0-9]{2}"),
+    ("MAC", r"%{CISCOMAC}|%{WINDOWSMAC}|%{COMMONMAC}"),
+    (
+        "IPV6",
+        r"(?:(?:[0-9A-Fa-f]{1,4}:){7}(?:[0-9A-Fa-f]{1,4}|:)|(?:[0-9A-Fa-f]{1,4}:){6}(?::[0-9A-Fa-f]{1,4}|%{IPV4}|:)|(?:[0-9A-Fa-f]{1,4}:){5}(?:(?::[0-9A-Fa-f]{1,4}){1,2}|:%{IPV4}|:)|(?:[0-9A-Fa-f]{1,4}:){4}(?:(?::[0-9A-Fa-f]{1,4}){1,3}|(?::[0-9A-Fa-f]{1,4})?:%{IPV4}|:)|(?:[0-9A-Fa-f]{1,4}:){3}(?:(?::[0-9A-Fa-f]{1,4}){1,4}|(?::[0-9A-Fa-f]{1,4}){0,2}:%{IPV4}|:)|(?:[0-9A-Fa-f]{1,4}:){2}(?:(?::[0-9A-Fa-f]{1,4}){1,5}|(?::[0-9A-Fa-f]{1,4}){0,3}:%{IPV4}|:)|(?:[0-9A-Fa-f]{1,4}:)(?:(?::[0-9A-Fa-f]{1,4}){1,6}|(?::[0-9A-Fa-f]{1,4}){0,4}:%{IPV4}|:)|:(?:(?::[0-9A-Fa-f]{1,4}){1,7}|(?::[0-9A-Fa-f]{1,4}){0,5}:%{IPV4}|:))(?:%.+)?",
+    ),
+    (
+        "IPV4",
+        r"(?:(?:25[0-5]|2[0-4][0-9]|[01]?[0-9]{1,2})\.){3}(?:25[0-5]|2[0-4][0-9]|[01]?[0-9]{1,2})",
+    ),
+    ("IP", r"%{IPV6}|%{IPV4}"),
+    (
+        "HOSTNAME",
+        r"\b[0-9A-Za-z][0-9A-Za-z-]{0,62}(?:\.[0-9A-Za-z][0-9A-Za-z-]{0,62})*(?:\.?|\b)",
+    ),
+    ("HOST", r"%{HOSTNAME}"),
+    ("IPORHOST", r"%{IP}|%{HOSTNAME}"),
+    ("HOSTPORT", r"%{IPORHOST}:%{POSINT}"),
+    ("PATH", r"%{UNIXPATH}|%{WINPATH}"),
+    ("UNIXPATH", r"(?:/(?:[\w_%!$@:.,+~-]+|\\.)*)+"),
+    ("TTY", r"/dev/(?:pts|tty(?:[pq])?)(?:\w+)?/?(?:[0-9]+)"),
+    ("WINPATH", r"(?:[A-Za-z]+:|\\)(?:\\[^\\?*]*)+"),
+    ("URIPROTO", r"[A-Za-z][A-Za-z0-9+\-.]+"),
+    ("URIHOST", r"%{IPORHOST}(?::%{POSINT})?"),
+    ("URIPATH", r"(?:/[A-Za-z0-9$.+!*'(){},~:;=@#%&_\-]*)+"),
+    ("URIPARAM", r"\?[A-Za-z0-9$.+!*'|(){},~@#%&/=:;_?\-\[\]<>]*"),
+    ("URIPATHPARAM", r"%{URIPATH}(?:%{URIPARAM})?"),
+    (
+        "URI",
+        r"%{URIPROTO}://(?:%{USER}(?::[^@]*)?@)?(?:%{URIHOST})?(?:%{URIPATHPARAM})?",
+    ),
+    (
+        "MONTH",
+        r"\b(?:[Jj]an(?:uary|uar)?|[Ff]eb(?:ruary|ruar)?|[Mm](?:a|ä)?r(?:ch|z)?|[Aa]pr(?:il)?|[Mm]a(?:y|i)?|[Jj]un(?:e|i)?|[Jj]ul(?:y|i)?|[Aa]ug(?:ust)?|[Ss]ep(?:tember)?|[Oo](?:c|k)?t(?:ober)?|[Nn]ov(?:ember)?|[Dd]e(?:c|z)(?:ember)?)\b",
+    ),
+    ("MONTHNUM", r"0?[1-9]|1[0-2]"),
+    ("MONTHNUM2", r"0[1-9]|1[0-2]"),
+    ("MONTHDAY", r"0[1-9]|[12][0-9]|3[01]|[1-9]"),
+    (
+        "DAY",
+        r"Mon(?:day)?|Tue(?:sday)?|Wed(?:nesday)?|Thu(?:rsday)?|Fri(?:day)?|Sat(?:urday)?|Sun(?:day)?",
+    ),
+    ("YEAR", r"(?:\d\d){1,2}"),
+    ("HOUR", r"2[0123]|[01]?[0-9]"),
+    ("MINUTE", r"[0-5][0-9]"),
+    ("SECOND", r"(?:[0-5]?[0-9]|60)(?:[:.,][0-9]+)?"),
+    ("TIME", r"%{HOUR}:%{MINUTE}(?::%{SECOND})?"),
+    ("DATE_US", r"%{MONTHNUM}[/-]%{MONTHDAY}[/-]%{YEAR}"),
+    ("DATE_EU", r"%{MONTHDAY}[./-]%{MONTHNUM}[./-]%{YEAR}"),
+    ("ISO8601_TIMEZONE", r"Z|[+-]%{HOUR}(?::?%{MINUTE})"),
+    ("ISO8601_SECOND", r"%{SECOND}|60"),
+    (
+        "TIMESTAMP_ISO8601",
+        r"%{YEAR}-%{MONTHNUM}-%{MONTHDAY}[T ]%{HOUR}:?%{MINUTE}(?::?%{SECOND})?%{ISO8601_TIMEZONE}?",
+    ),
+    ("DATE", r"%{DATE_US}|%{DATE_EU}"),
+    ("DATESTAMP", r"%{DATE}[- ]%{TIME}"),
+    ("TZ", r"[APMCE][SD]T|UTC"),
+    (
+        "DATESTAMP_RFC822",
+        r"%{DAY} %{MONTH} %{MONTHDAY} %{YEAR} %{TIME} %{TZ}",
+    ),
+    (
+        "DATESTAMP_RFC2822",
+        r"%{DAY}, %{MONTHDAY} %{MONTH} %{YEAR} %{TIME} %{ISO8601_TIMEZONE}",
+    ),
+    (
+        "DATESTAMP_OTHER",
+        r"%{DAY} %{MONTH} %{MONTHDAY} %{TIME} %{TZ} %{YEAR}",
+    ),
+    (
+        "DATESTAMP_EVENTLOG",
+        r"%{YEAR}%{MONTHNUM2}%{MONTHDAY}%{HOUR}%{MINUTE}%{SECOND}",
+    ),
+    ("HTTPDATE", r"%{MONTHDAY}/%{MONTH}/%{YEAR}:%{TIME} %{INT}"),
+    ("SYSLOGTIMESTAMP", r"%{MONTH} +%{MONTHDAY} %{TIME}"),
+    ("PROG", r"[\x21-\x5a\x5c\x5e-\x7e]+"),
+    ("SYSLOGPROG", r"%{PROG:program}(?:\[%{POSINT:pid}\])?"),
+    ("SYSLOGHOST", r"%{IPORHOST}"),
+    (
+        "SYSLOGFACILITY",
+        r"<%{NONNEGINT:facility}.%{NONNEGINT:priority}>",
+    ),
+    (
+        "SYSLOGBASE",
+        r"%{SYSLOGTIMESTAMP:timestamp} (?:%{SYSLOGFACILITY} )?%{SYSLOGHOST:logsource} %{SYSLOGPROG}:",
+    ),
+    (
+        "LOGLEVEL",
+        r"[Aa]lert|ALERT|[Tt]race|TRACE|[Dd]ebug|DEBUG|[Nn]otice|NOTICE|[Ii]nfo|INFO|[Ww]arn?(?:ing)?|WARN?(?:ING)?|[Ee]rr?(?:or)?|ERR?(?:OR)?|[Cc]rit?(?:ical)?|CRIT?(?:ICAL)?|[Ff]atal|FATAL|[Ss]evere|SEVERE|EMERG(?:ENCY)?|[Ee]merg(?:ency)?",
+    ),
+    ("HTTPDUSER", r"%{EMAILADDRESS}|%{USER}"),
+    (
+        "COMMONAPACHELOG",
+        r#"%{IPORHOST:clientip} %{HTTPDUSER:ident} %{USER:auth} \[%{HTTPDATE:timestamp}\] "(?:%{WORD:verb} %{NOTSPACE:request}(?: HTTP/%{NUMBER:httpversion})?|%{DATA:rawrequest})" %{NUMBER:response} (?:%{NUMBER:bytes}|-)"#,
+    ),
+    (
+        "COMBINEDAPACHELOG",
+        r"%{COMMONAPACHELOG} %{QS:referrer} %{QS:agent}",
+    ),
+];
+
+/// Expands the `%{PATTERN}` and `%{PATTERN:field}` references in `pattern`
+/// into a regular expression with a named capture group per field.
+pub fn expand(pattern: &str) -> Result<String, anyhow::Error> {
+    let mut out = String::with_capacity(pattern.len());
+    let mut fields = vec![];
+    expand_into(pattern, &mut out, &mut fields)?;
+    Ok(out)
+}
+
+fn expand_into<'a>(
+    pattern: &'a str,
+    out: &mut String,
+    fields: &mut Vec<&'a str>,
+) -> Result<(), anyhow::Error> {
+    let mut rest = pattern;
+    while let Some(start) = rest.find("%{") {
+        out.push_str(&rest[..start]);
+        let reference = &rest[start + 2..];
+        let end = match reference.find('}') {
+            Some(end) => end,
+            None => bail!("unterminated grok pattern reference: %{{{}", reference),
+        };
+        let mut parts = reference[..end].splitn(3, ':');
+        let name = parts.next().unwrap();
+        let field = parts.next();
+        if parts.next().is_some() {
+            bail!(
+                "grok type conversions are not supported: %{{{}}}; cast the column instead",
+                &reference[..end]
+            );
+        }
+        let definition = match PATTERNS.iter().find(|(n, _)| *n == name) {
+            Some((_, definition)) => definition,
+            None => bail!("unknown grok pattern: %{{{}}}", name),
+        };
+        match field {
+            Some(field) => {
+                let mut chars = field.chars();
+                let valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !valid {
+                    bail!("invalid grok field name: {}", field);
+                }
+                if fields.contains(&field) {
+                    bail!("grok field {} is captured more than once", field);
+                }
+                fields.push(field);
+                out.push_str("(?P<");
+                out.push_str(field);
+                out.push('>');
+            }
+            None => out.push_str("(?:"),
+        }
+        expand_into(definition, out, fields)?;
+        out.push(')');
+        rest = &reference[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::{expand, PATTERNS};
+
+    #[test]
+    fn test_library() {
+        for (name, _) in PATTERNS {
+            let expanded = expand(&format!("%{{{}}}", name)).unwrap();
+            let regex = Regex::new(&expanded).unwrap();
+            // Library patterns must only capture named fields.
+            assert_eq!(
+                regex.captures_len() - 1,
+                regex.capture_names().flatten().count(),
+                "{} has unnamed capture groups",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(expand(r"(\w+) .*").unwrap(), r"(\w+) .*");
+        assert_eq!(
+            expand("%{INT:n} %{WORD}").unwrap(),
+            r"(?P<n>[+-]?[0-9]+) (?:\b\w+\b)"
+        );
+
+        let regex = Regex::new(&expand("%{COMBINEDAPACHELOG}").unwrap()).unwrap();
+        let captures = regex
+            .captures(
+                r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08""#,
+            )
+            .unwrap();
+        assert_eq!(&captures["clientip"], "127.0.0.1");
+        assert_eq!(&captures["auth"], "frank");
+        assert_eq!(&captures["timestamp"], "10/Oct/2000:13:55:36 -0700");
+        assert_eq!(&captures["verb"], "GET");
+        assert_eq!(&captures["request"], "/apache_pb.gif");
+        assert_eq!(&captures["httpversion"], "1.0");
+        assert_eq!(&captures["response"], "200");
+        assert_eq!(&captures["bytes"], "2326");
+        assert_eq!(
+            &captures["referrer"],
+            r#""http://www.example.com/start.html""#
+        );
+        assert!(captures.name("rawrequest").is_none());
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        assert!(expand("%{INT").is_err());
+        assert!(expand("%{NOPE}").is_err());
+        assert!(expand("%{INT:n:int}").is_err());
+        assert!(expand("%{INT:a.b}").is_err());
+        assert!(expand("%{INT:n} %{INT:n}").is_err());
+    }
+}
@@ -92,6 +92,7 @@ macro_rules! sql_bail {
 pub mod ast;
 pub mod catalog;
 pub mod func;
+pub mod grok;
 pub mod kafka_util;
 pub mod names;
 #[macro_use]
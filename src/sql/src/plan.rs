@@ -104,6 +104,7 @@ pub enum Plan {
     AlterIndexSetOptions(AlterIndexSetOptionsPlan),
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterIndexEnable(AlterIndexEnablePlan),
+    AlterSource(AlterSourcePlan),
     AlterItemRename(AlterItemRenamePlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
@@ -318,6 +319,12 @@ pub struct AlterIndexEnablePlan {
     pub id: GlobalId,
 }
 
+#[derive(Debug)]
+pub struct AlterSourcePlan {
+    pub id: GlobalId,
+    pub paused: bool,
+}
+
 #[derive(Debug)]
 pub struct AlterItemRenamePlan {
     pub id: GlobalId,
@@ -116,6 +116,7 @@ pub fn describe(
         Statement::DropObjects(stmt) => ddl::describe_drop_objects(&scx, stmt)?,
         Statement::AlterObjectRename(stmt) => ddl::describe_alter_object_rename(&scx, stmt)?,
         Statement::AlterIndex(stmt) => ddl::describe_alter_index_options(&scx, stmt)?,
+        Statement::AlterSource(stmt) => ddl::describe_alter_source(&scx, stmt)?,
 
         // `SHOW` statements.
         Statement::ShowColumns(stmt) => show::show_columns(&scx, stmt)?.describe()?,
@@ -145,6 +146,7 @@ pub fn describe(
         Statement::Delete(stmt) => dml::describe_delete(&scx, stmt)?,
         Statement::Select(stmt) => dml::describe_select(&scx, stmt)?,
         Statement::Explain(stmt) => dml::describe_explain(&scx, stmt)?,
+        Statement::ExplainSource(stmt) => show::describe_explain_source(&scx, stmt)?,
         Statement::Tail(stmt) => dml::describe_tail(&scx, stmt)?,
         Statement::Copy(stmt) => dml::describe_copy(&scx, stmt)?,
 
@@ -205,6 +207,7 @@ pub fn plan(
         Statement::DropDatabase(stmt) => ddl::plan_drop_database(scx, stmt),
         Statement::DropObjects(stmt) => ddl::plan_drop_objects(scx, stmt),
         Statement::AlterIndex(stmt) => ddl::plan_alter_index_options(scx, stmt),
+        Statement::AlterSource(stmt) => ddl::plan_alter_source(scx, stmt),
         Statement::AlterObjectRename(stmt) => ddl::plan_alter_object_rename(scx, stmt),
 
         // DML statements.
@@ -213,6 +216,7 @@ pub fn plan(
         Statement::Delete(stmt) => dml::plan_delete(scx, stmt, params),
         Statement::Select(stmt) => dml::plan_select(scx, stmt, params, None),
         Statement::Explain(stmt) => dml::plan_explain(scx, stmt, params),
+        Statement::ExplainSource(stmt) => show::plan_explain_source(scx, stmt),
         Statement::Tail(stmt) => dml::plan_tail(scx, stmt, None),
         Statement::Copy(stmt) => dml::plan_copy(scx, stmt),
 
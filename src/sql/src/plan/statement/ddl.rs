@@ -36,13 +36,16 @@ use mz_dataflow_types::{
     },
     sources::{
         encoding::{
-            included_column_desc, AvroEncoding, AvroOcfEncoding, ColumnSpec, CsvEncoding,
-            DataEncoding, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
+            included_column_desc, AccessLogEncoding, AvroEncoding, AvroOcfEncoding, ColumnSpec,
+            CsvEncoding, DataEncoding, InvalidJsonPolicy, JsonEncoding, OtlpSignal,
+            ProtobufEncoding, RegexEncoding, SourceDataEncoding,
         },
         provide_default_metadata, DebeziumDedupProjection, DebeziumEnvelope, DebeziumMode,
-        DebeziumSourceProjection, ExternalSourceConnector, FileSourceConnector, IncludedColumnPos,
-        KafkaSourceConnector, KeyEnvelope, KinesisSourceConnector, PostgresSourceConnector,
-        PubNubSourceConnector, S3SourceConnector, SourceConnector, SourceEnvelope, Timeline,
+        DebeziumSourceProjection, ExternalSourceConnector, FileSourceConnector,
+        FluentSourceConnector, IncludedColumnPos, KafkaSourceConnector, KeyEnvelope,
+        KinesisSourceConnector, OtlpSourceConnector, PostgresSourceConnector,
+        PrometheusSourceConnector, PubNubSourceConnector, S3SourceConnector, SourceConnector,
+        SourceEnvelope, SyslogSourceConnector, SyslogTlsConfig, TempoSourceConnector, Timeline,
         UnplannedSourceEnvelope, UpsertStyle,
     },
 };
@@ -56,18 +59,19 @@ use mz_sql_parser::ast::{CsrSeedCompiledOrLegacy, SourceIncludeMetadata};
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
-    AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement, AvroSchema, ColumnOption,
-    Compression, CreateDatabaseStatement, CreateIndexStatement, CreateRoleOption,
-    CreateRoleStatement, CreateSchemaStatement, CreateSinkConnector, CreateSinkStatement,
-    CreateSourceConnector, CreateSourceFormat, CreateSourceStatement, CreateTableStatement,
-    CreateTypeAs, CreateTypeStatement, CreateViewStatement, CreateViewsDefinitions,
-    CreateViewsStatement, CsrConnectorAvro, CsrConnectorProto, CsrSeedCompiled, CsvColumns,
-    DbzMode, DropDatabaseStatement, DropObjectsStatement, Envelope, Expr, Format, Ident,
-    IfExistsBehavior, KafkaConsistency, KeyConstraint, ObjectType, ProtobufSchema, Raw,
-    SourceIncludeMetadataType, SqlOption, Statement, TableConstraint, UnresolvedObjectName, Value,
-    ViewDefinition, WithOption,
+    AccessLogFormat, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
+    AlterSourceAction, AlterSourceStatement, AvroSchema, ColumnOption, Compression,
+    CreateDatabaseStatement, CreateIndexStatement, CreateRoleOption, CreateRoleStatement,
+    CreateSchemaStatement, CreateSinkConnector, CreateSinkStatement, CreateSourceConnector,
+    CreateSourceFormat, CreateSourceStatement, CreateTableStatement, CreateTypeAs,
+    CreateTypeStatement, CreateViewStatement, CreateViewsDefinitions, CreateViewsStatement,
+    CsrConnectorAvro, CsrConnectorProto, CsrSeedCompiled, CsvColumns, DbzMode,
+    DropDatabaseStatement, DropObjectsStatement, Envelope, Expr, Format, Ident, IfExistsBehavior,
+    KafkaConsistency, KeyConstraint, ObjectType, ProtobufSchema, Raw, SourceIncludeMetadataType,
+    SqlOption, Statement, TableConstraint, UnresolvedObjectName, Value, ViewDefinition, WithOption,
 };
 use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails};
+use crate::grok;
 use crate::kafka_util;
 use crate::names::{
     resolve_names_data_type, DatabaseSpecifier, FullName, ResolvedDataType, SchemaName,
@@ -79,11 +83,11 @@ use crate::plan::query::QueryLifetime;
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{
     plan_utils, query, AlterIndexEnablePlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
-    AlterItemRenamePlan, AlterNoopPlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan,
-    CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
-    CreateViewPlan, CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan,
-    DropSchemaPlan, HirRelationExpr, Index, IndexOption, IndexOptionName, Params, Plan, Sink,
-    Source, Table, Type, View,
+    AlterItemRenamePlan, AlterNoopPlan, AlterSourcePlan, CreateDatabasePlan, CreateIndexPlan,
+    CreateRolePlan, CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
+    CreateTypePlan, CreateViewPlan, CreateViewsPlan, DropDatabasePlan, DropItemsPlan,
+    DropRolesPlan, DropSchemaPlan, HirRelationExpr, Index, IndexOption, IndexOptionName, Params,
+    Plan, Sink, Source, Table, Type, View,
 };
 use crate::pure::Schema;
 
@@ -459,6 +463,7 @@ pub fn plan_create_source(
             if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
                 bail!("File sources do not support key decoding");
             }
+            check_newline_delimited_encoding("File", &encoding)?;
             (connector, encoding)
         }
         CreateSourceConnector::S3 {
@@ -504,6 +509,7 @@ pub fn plan_create_source(
             if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
                 bail!("S3 sources do not support key decoding");
             }
+            check_newline_delimited_encoding("S3", &encoding)?;
             (connector, encoding)
         }
         CreateSourceConnector::Postgres {
@@ -543,6 +549,112 @@ pub fn plan_create_source(
             });
             (connector, SourceDataEncoding::Single(DataEncoding::Text))
         }
+        CreateSourceConnector::PrometheusRemoteWrite { addr } => {
+            if !matches!(format, CreateSourceFormat::None) {
+                bail!("CREATE SOURCE ... PROMETHEUS does not support FORMAT");
+            }
+            let addr = addr
+                .parse()
+                .map_err(|_| anyhow!("invalid remote write address: {}", addr))?;
+            let connector = PrometheusSourceConnector::RemoteWrite { addr };
+            let connector = ExternalSourceConnector::Prometheus(connector);
+            let encoding = SourceDataEncoding::Single(DataEncoding::Prometheus);
+            (connector, encoding)
+        }
+        CreateSourceConnector::PrometheusScrape { url, interval } => {
+            if !matches!(format, CreateSourceFormat::None) {
+                bail!("CREATE SOURCE ... PROMETHEUS does not support FORMAT");
+            }
+            let url = url.parse::<Url>()?;
+            if !matches!(url.scheme(), "http" | "https") {
+                bail!("scrape URL must use http or https: {}", url);
+            }
+            let interval = mz_repr::util::parse_duration(interval)?;
+            if interval == Duration::ZERO {
+                bail!("scrape interval must be positive");
+            }
+            let connector = PrometheusSourceConnector::Scrape { url, interval };
+            let connector = ExternalSourceConnector::Prometheus(connector);
+            let encoding = SourceDataEncoding::Single(DataEncoding::Prometheus);
+            (connector, encoding)
+        }
+        CreateSourceConnector::Otlp { signal, addr } => {
+            if !matches!(format, CreateSourceFormat::None) {
+                bail!("CREATE SOURCE ... OTLP does not support FORMAT");
+            }
+            let addr = addr
+                .parse()
+                .map_err(|_| anyhow!("invalid OTLP listen address: {}", addr))?;
+            let signal = match signal {
+                crate::ast::OtlpSignal::Logs => OtlpSignal::Logs,
+                crate::ast::OtlpSignal::Traces => OtlpSignal::Traces,
+                crate::ast::OtlpSignal::Metrics => OtlpSignal::Metrics,
+            };
+            let connector = ExternalSourceConnector::Otlp(OtlpSourceConnector { addr, signal });
+            let encoding = SourceDataEncoding::Single(DataEncoding::Otlp(signal));
+            (connector, encoding)
+        }
+        CreateSourceConnector::Tempo {
+            url,
+            tags,
+            interval,
+        } => {
+            if !matches!(format, CreateSourceFormat::None) {
+                bail!("CREATE SOURCE ... TEMPO does not support FORMAT");
+            }
+            let url = url.parse::<Url>()?;
+            if !matches!(url.scheme(), "http" | "https") {
+                bail!("Tempo URL must use http or https: {}", url);
+            }
+            let interval = mz_repr::util::parse_duration(interval)?;
+            if interval == Duration::ZERO {
+                bail!("search interval must be positive");
+            }
+            let connector = ExternalSourceConnector::Tempo(TempoSourceConnector {
+                url,
+                tags: tags.clone(),
+                interval,
+            });
+            // Tempo serves traces as OTLP, so they decode into the same spans.
+            let encoding = SourceDataEncoding::Single(DataEncoding::Otlp(OtlpSignal::Traces));
+            (connector, encoding)
+        }
+        CreateSourceConnector::Syslog { addr } => {
+            if !matches!(format, CreateSourceFormat::None) {
+                bail!("CREATE SOURCE ... SYSLOG does not support FORMAT");
+            }
+            let addr = addr
+                .parse()
+                .map_err(|_| anyhow!("invalid syslog listen address: {}", addr))?;
+            let mut path_option = |name: &str| match with_options.remove(name) {
+                None => Ok(None),
+                Some(Value::String(path)) => Ok(Some(PathBuf::from(path))),
+                Some(_) => Err(anyhow!("{} must be a string", name)),
+            };
+            let cert = path_option("tls_certificate_location")?;
+            let key = path_option("tls_key_location")?;
+            let tls = match (cert, key) {
+                (None, None) => None,
+                (Some(cert), Some(key)) => Some(SyslogTlsConfig { cert, key }),
+                _ => bail!(
+                    "tls_certificate_location and tls_key_location must be specified together"
+                ),
+            };
+            let connector = ExternalSourceConnector::Syslog(SyslogSourceConnector { addr, tls });
+            let encoding = SourceDataEncoding::Single(DataEncoding::Syslog);
+            (connector, encoding)
+        }
+        CreateSourceConnector::Fluent { addr } => {
+            if !matches!(format, CreateSourceFormat::None) {
+                bail!("CREATE SOURCE ... FLUENT does not support FORMAT");
+            }
+            let addr = addr
+                .parse()
+                .map_err(|_| anyhow!("invalid forward protocol listen address: {}", addr))?;
+            let connector = ExternalSourceConnector::Fluent(FluentSourceConnector { addr });
+            let encoding = SourceDataEncoding::Single(DataEncoding::Fluent);
+            (connector, encoding)
+        }
         CreateSourceConnector::AvroOcf { path, .. } => {
             let tail = match with_options.remove("tail") {
                 None => false,
@@ -753,6 +865,16 @@ pub fn plan_create_source(
         col_names
     };
 
+    // The encoding is planned from a normalized copy of the options, so
+    // `invalid_json` is still present here. Remove it, and reject it for sources
+    // that don't decode JSON.
+    if with_options.remove("invalid_json").is_some()
+        && !matches!(encoding.value_ref(), DataEncoding::Json(_))
+        && !matches!(encoding.key_ref(), Some(DataEncoding::Json(_)))
+    {
+        bail!("invalid_json is only supported for FORMAT JSON sources");
+    }
+
     let ignore_source_keys = match with_options.remove("ignore_source_keys") {
         None => false,
         Some(Value::Boolean(b)) => b,
@@ -805,6 +927,27 @@ pub fn plan_create_source(
     let name = scx.allocate_name(normalize::unresolved_object_name(name.clone())?);
     let create_sql = normalize::create_statement(&scx, Statement::CreateSource(stmt))?;
 
+    let retention = match with_options.remove("retention") {
+        None => None,
+        Some(Value::String(s)) => {
+            if !matches!(envelope, SourceEnvelope::None(_)) {
+                bail!("retention is only supported for sources with ENVELOPE NONE");
+            }
+            // Retention retracts every update once it expires, which is only
+            // sound for sources that never retract rows themselves: a deleted
+            // row would otherwise be retracted twice.
+            if let ExternalSourceConnector::Postgres(_) = &external_connector {
+                bail!("retention is not supported for POSTGRES sources");
+            }
+            let retention = mz_repr::util::parse_duration(&s)?;
+            if retention.is_zero() {
+                bail!("retention must be greater than zero");
+            }
+            Some(retention)
+        }
+        Some(_) => bail!("retention must be a string"),
+    };
+
     // Allow users to specify a timeline. If they do not, determine a default timeline for the source.
     let timeline = if let Some(timeline) = with_options.remove("timeline") {
         match timeline {
@@ -837,6 +980,7 @@ pub fn plan_create_source(
             metadata_columns: metadata_column_types,
             ts_frequency,
             timeline,
+            retention,
         },
         expr,
         desc,
@@ -1190,6 +1334,12 @@ fn get_encoding_inner<T: mz_sql_parser::ast::AstInfo>(
                 regex: mz_repr::adt::regex::Regex(regex),
             })
         }
+        Format::Grok(pattern) => {
+            let regex = Regex::new(&grok::expand(pattern)?)?;
+            DataEncoding::Regex(RegexEncoding {
+                regex: mz_repr::adt::regex::Regex(regex),
+            })
+        }
         Format::Csv { columns, delimiter } => {
             let columns = match columns {
                 CsvColumns::Header { names } => {
@@ -1210,11 +1360,49 @@ fn get_encoding_inner<T: mz_sql_parser::ast::AstInfo>(
                 },
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
+        Format::Json => {
+            let invalid = match normalize::options(with_options).remove("invalid_json") {
+                None => InvalidJsonPolicy::Error,
+                Some(Value::String(s)) => match s.as_str() {
+                    "error" => InvalidJsonPolicy::Error,
+                    "null" => InvalidJsonPolicy::Null,
+                    "text" => InvalidJsonPolicy::Text,
+                    _ => bail!("invalid_json must be one of 'error', 'null' or 'text'"),
+                },
+                Some(_) => bail!("invalid_json must be one of 'error', 'null' or 'text'"),
+            };
+            DataEncoding::Json(JsonEncoding { invalid })
+        }
         Format::Text => DataEncoding::Text,
+        Format::Logfmt => DataEncoding::Logfmt,
+        Format::Syslog => DataEncoding::Syslog,
+        Format::Cef => DataEncoding::Cef,
+        Format::AccessLog(format) => DataEncoding::AccessLog(match format {
+            AccessLogFormat::Common => AccessLogEncoding::Common,
+            AccessLogFormat::Combined => AccessLogEncoding::Combined,
+        }),
+        Format::Msgpack => DataEncoding::Msgpack,
+        Format::Cbor => DataEncoding::Cbor,
     }))
 }
 
+/// Rejects binary encodings for sources that split their data into records at
+/// newlines, as binary records can themselves contain newlines.
+fn check_newline_delimited_encoding(
+    source: &str,
+    encoding: &SourceDataEncoding,
+) -> Result<(), anyhow::Error> {
+    match encoding {
+        SourceDataEncoding::Single(DataEncoding::Msgpack) => {
+            bail!("{} sources do not support FORMAT MSGPACK", source)
+        }
+        SourceDataEncoding::Single(DataEncoding::Cbor) => {
+            bail!("{} sources do not support FORMAT CBOR", source)
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Extract the key envelope, if it is requested
 fn get_key_envelope(
     included_items: &[SourceIncludeMetadata],
@@ -1238,14 +1426,26 @@ fn get_key_envelope(
                 //
                 // Otherwise it gets the names of the columns in the type
                 let is_composite = match key {
-                    DataEncoding::AvroOcf { .. } | DataEncoding::Postgres => {
+                    DataEncoding::AvroOcf { .. }
+                    | DataEncoding::Postgres
+                    | DataEncoding::Prometheus
+                    | DataEncoding::Otlp(_)
+                    | DataEncoding::Fluent => {
                         bail!("{} sources cannot use INCLUDE KEY", key.op_name())
                     }
-                    DataEncoding::Bytes | DataEncoding::Text => false,
+                    DataEncoding::Bytes
+                    | DataEncoding::Text
+                    | DataEncoding::Logfmt
+                    | DataEncoding::Json(_)
+                    | DataEncoding::Msgpack
+                    | DataEncoding::Cbor => false,
                     DataEncoding::Avro(_)
                     | DataEncoding::Csv(_)
                     | DataEncoding::Protobuf(_)
-                    | DataEncoding::Regex { .. } => true,
+                    | DataEncoding::Regex { .. }
+                    | DataEncoding::Syslog
+                    | DataEncoding::Cef
+                    | DataEncoding::AccessLog(_) => true,
                 };
 
                 if is_composite {
@@ -2581,6 +2781,43 @@ pub fn plan_alter_index_options(
     }
 }
 
+pub fn describe_alter_source(
+    _: &StatementContext,
+    _: AlterSourceStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_source(
+    scx: &StatementContext,
+    AlterSourceStatement {
+        source_name,
+        if_exists,
+        action,
+    }: AlterSourceStatement,
+) -> Result<Plan, anyhow::Error> {
+    let entry = match scx.resolve_item(source_name) {
+        Ok(source) => source,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Source,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if entry.item_type() != CatalogItemType::Source {
+        bail!("{} is a {} not a source", entry.name(), entry.item_type())
+    }
+    let paused = match action {
+        AlterSourceAction::Pause => true,
+        AlterSourceAction::Resume => false,
+    };
+    Ok(Plan::AlterSource(AlterSourcePlan {
+        id: entry.id(),
+        paused,
+    }))
+}
+
 pub fn describe_alter_object_rename(
     _: &StatementContext,
     _: AlterObjectRenameStatement,
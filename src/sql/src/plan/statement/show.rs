@@ -19,12 +19,14 @@ use mz_ore::collections::CollectionExt;
 use mz_repr::{Datum, RelationDesc, Row, ScalarType};
 use mz_sql_parser::ast::display::AstDisplay;
 
-use crate::ast::visit_mut::VisitMut;
+use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
-    ObjectType, Raw, SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement,
-    ShowCreateSinkStatement, ShowCreateSourceStatement, ShowCreateTableStatement,
-    ShowCreateViewStatement, ShowDatabasesStatement, ShowIndexesStatement, ShowObjectsStatement,
-    ShowStatementFilter, Statement, UnresolvedObjectName, Value,
+    CreateSourceConnector, CreateSourceFormat, CsrConnectorAvro, CsrConnectorProto,
+    ExplainSourceStatement, ObjectType, Raw, SelectStatement, ShowColumnsStatement,
+    ShowCreateIndexStatement, ShowCreateSinkStatement, ShowCreateSourceStatement,
+    ShowCreateTableStatement, ShowCreateViewStatement, ShowDatabasesStatement,
+    ShowIndexesStatement, ShowObjectsStatement, ShowStatementFilter, SqlOption, Statement,
+    UnresolvedObjectName, Value,
 };
 use crate::catalog::CatalogItemType;
 use crate::names::{resolve_names_stmt, NameSimplifier};
@@ -132,6 +134,214 @@ pub fn plan_show_create_source(
     }
 }
 
+pub fn describe_explain_source(
+    _: &StatementContext,
+    _: ExplainSourceStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(Some(
+        RelationDesc::empty()
+            .with_column("Property", ScalarType::String.nullable(false))
+            .with_column("Value", ScalarType::String.nullable(false)),
+    )))
+}
+
+/// Describes how a source ingests and decodes its data, one property per row,
+/// as recorded in the catalog.
+pub fn plan_explain_source(
+    scx: &StatementContext,
+    ExplainSourceStatement { source_name }: ExplainSourceStatement,
+) -> Result<Plan, anyhow::Error> {
+    let source = scx.resolve_item(source_name)?;
+    if source.item_type() != CatalogItemType::Source {
+        bail!("{} is not a source", source.name());
+    }
+    let mut stmt = match parse::parse(source.create_sql())?.into_element() {
+        Statement::CreateSource(stmt) => stmt,
+        _ => bail!("{} is not a source", source.name()),
+    };
+    SecretRedactor.visit_create_source_statement_mut(&mut stmt);
+
+    let mut properties = vec![
+        ("name", source.name().to_string()),
+        ("connector", stmt.connector.to_ast_string()),
+    ];
+    for option in &stmt.with_options {
+        properties.push(("option", option.to_ast_string()));
+    }
+    let format = match &stmt.format {
+        CreateSourceFormat::None => "NONE".to_string(),
+        format => format.to_ast_string().trim_start().to_string(),
+    };
+    properties.push(("format", format));
+    properties.push(("envelope", stmt.envelope.to_ast_string()));
+    for metadata in &stmt.include_metadata {
+        properties.push(("include", metadata.to_ast_string()));
+    }
+    for (name, typ) in source.desc()?.iter() {
+        let typ = scx.humanize_scalar_type(&typ.scalar_type);
+        properties.push(("column", format!("{} {}", name.as_str(), typ)));
+    }
+
+    Ok(Plan::SendRows(SendRowsPlan {
+        rows: properties
+            .iter()
+            .map(|(property, value)| {
+                Row::pack_slice(&[Datum::String(property), Datum::String(value)])
+            })
+            .collect(),
+    }))
+}
+
+/// The options whose values are credentials, which `EXPLAIN SOURCE` doesn't
+/// print.
+const SECRET_OPTIONS: &[&str] = &[
+    "password",
+    "sasl_password",
+    "secret_access_key",
+    "ssl_key_password",
+    "token",
+];
+
+/// Replaces the values of secret options, including those of a schema
+/// registry, with a placeholder, as well as the credentials that connection
+/// strings and URLs embed.
+struct SecretRedactor;
+
+impl<'ast> VisitMut<'ast, Raw> for SecretRedactor {
+    fn visit_sql_option_mut(&mut self, option: &'ast mut SqlOption<Raw>) {
+        if let SqlOption::Value { name, value } = option {
+            if SECRET_OPTIONS.contains(&name.as_str()) {
+                *value = Value::String("<redacted>".into());
+            }
+        }
+    }
+
+    fn visit_create_source_connector_mut(&mut self, connector: &'ast mut CreateSourceConnector) {
+        match connector {
+            CreateSourceConnector::Postgres { conn, .. } => *conn = redact_connection_string(conn),
+            CreateSourceConnector::PrometheusScrape { url, .. }
+            | CreateSourceConnector::Tempo { url, .. } => *url = redact_url(url),
+            _ => (),
+        }
+        visit_mut::visit_create_source_connector_mut(self, connector);
+    }
+
+    fn visit_csr_connector_avro_mut(&mut self, csr: &'ast mut CsrConnectorAvro<Raw>) {
+        csr.url = redact_url(&csr.url);
+        visit_mut::visit_csr_connector_avro_mut(self, csr);
+    }
+
+    fn visit_csr_connector_proto_mut(&mut self, csr: &'ast mut CsrConnectorProto<Raw>) {
+        csr.url = redact_url(&csr.url);
+        visit_mut::visit_csr_connector_proto_mut(self, csr);
+    }
+}
+
+/// Replaces the values of secret parameters of a Postgres connection string,
+/// in either its `key=value` or its URI form, with a placeholder.
+fn redact_connection_string(conn: &str) -> String {
+    if conn.contains("://") {
+        return redact_url(conn);
+    }
+    let mut out = String::with_capacity(conn.len());
+    let mut rest = conn;
+    loop {
+        let trimmed = rest.trim_start();
+        out.push_str(&rest[..rest.len() - trimmed.len()]);
+        rest = trimmed;
+        if rest.is_empty() {
+            return out;
+        }
+
+        let key_len = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = &rest[..key_len];
+        let value_start = match rest[key_len..].trim_start().strip_prefix('=') {
+            Some(value) => rest.len() - value.trim_start().len(),
+            // Malformed connection strings are rejected when the source is
+            // created, so this doesn't happen.
+            None => {
+                out.push_str(rest);
+                return out;
+            }
+        };
+        out.push_str(&rest[..value_start]);
+        rest = &rest[value_start..];
+
+        // Values are either single-quoted, with backslash escapes, or end at
+        // the next whitespace.
+        let value_len = if rest.starts_with('\'') {
+            let mut escaped = false;
+            rest.char_indices()
+                .skip(1)
+                .find(|(_, c)| {
+                    let end = !escaped && *c == '\'';
+                    escaped = !escaped && *c == '\\';
+                    end
+                })
+                .map_or(rest.len(), |(i, _)| i + 1)
+        } else {
+            rest.find(char::is_whitespace).unwrap_or(rest.len())
+        };
+        if SECRET_OPTIONS.contains(&key) {
+            out.push_str("<redacted>");
+        } else {
+            out.push_str(&rest[..value_len]);
+        }
+        rest = &rest[value_len..];
+    }
+}
+
+/// Replaces the userinfo of a URL, and the values of secret query
+/// parameters, with a placeholder.
+fn redact_url(url: &str) -> String {
+    let authority_start = match url.find("://") {
+        Some(i) => i + 3,
+        None => return url.to_string(),
+    };
+    let authority_end = url[authority_start..]
+        .find(|c| matches!(c, '/' | '?' | '#'))
+        .map_or(url.len(), |i| authority_start + i);
+    let authority = &url[authority_start..authority_end];
+
+    let mut out = String::with_capacity(url.len());
+    out.push_str(&url[..authority_start]);
+    match authority.rfind('@') {
+        Some(at) => {
+            out.push_str("<redacted>");
+            out.push_str(&authority[at..]);
+        }
+        None => out.push_str(authority),
+    }
+
+    let rest = &url[authority_end..];
+    let (path, query) = match rest.find('?') {
+        Some(i) => (&rest[..i], &rest[i + 1..]),
+        None => return out + rest,
+    };
+    let (query, fragment) = match query.find('#') {
+        Some(i) => (&query[..i], &query[i..]),
+        None => (query, ""),
+    };
+    out.push_str(path);
+    out.push('?');
+    for (i, param) in query.split('&').enumerate() {
+        if i > 0 {
+            out.push('&');
+        }
+        match param.split_once('=') {
+            Some((name, _)) if SECRET_OPTIONS.contains(&name) => {
+                out.push_str(name);
+                out.push_str("=<redacted>");
+            }
+            _ => out.push_str(param),
+        }
+    }
+    out.push_str(fragment);
+    out
+}
+
 pub fn describe_show_create_sink(
     _: &StatementContext,
     _: ShowCreateSinkStatement,
@@ -627,3 +837,31 @@ impl<'a> ShowSelect<'a> {
         dml::plan_select(self.scx, self.stmt, &Params::empty(), None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::collections::CollectionExt;
+    use mz_sql_parser::ast::display::AstDisplay;
+
+    use super::SecretRedactor;
+    use crate::ast::visit_mut::VisitMut;
+    use crate::ast::Statement;
+    use crate::parse;
+
+    #[test]
+    fn test_secret_redactor() {
+        let sql = "CREATE SOURCE s FROM KAFKA BROKER 'b' TOPIC 't' \
+                   WITH (sasl_username = 'u', sasl_password = 'p') \
+                   FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY 'r' \
+                   WITH (username = 'u', password = 'p')";
+        let mut stmt = match parse::parse(sql).unwrap().into_element() {
+            Statement::CreateSource(stmt) => stmt,
+            _ => unreachable!(),
+        };
+        SecretRedactor.visit_create_source_statement_mut(&mut stmt);
+        let redacted = stmt.to_ast_string();
+        assert!(!redacted.contains("'p'"), "{}", redacted);
+        assert!(redacted.contains("sasl_username = 'u'"), "{}", redacted);
+        assert_eq!(redacted.matches("password = '<redacted>'").count(), 2);
+    }
+}
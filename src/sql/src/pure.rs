@@ -211,7 +211,13 @@ pub fn purify(
                     };
                     *details = Some(hex::encode(details_proto.encode_to_vec()));
                 }
-                CreateSourceConnector::PubNub { .. } => (),
+                CreateSourceConnector::PubNub { .. }
+                | CreateSourceConnector::PrometheusRemoteWrite { .. }
+                | CreateSourceConnector::PrometheusScrape { .. }
+                | CreateSourceConnector::Otlp { .. }
+                | CreateSourceConnector::Tempo { .. }
+                | CreateSourceConnector::Syslog { .. }
+                | CreateSourceConnector::Fluent { .. } => (),
             }
 
             purify_source_format(
@@ -544,7 +550,17 @@ async fn purify_source_format_single(
         } => {
             purify_csv(file, connector, *delimiter, columns).await?;
         }
-        Format::Bytes | Format::Regex(_) | Format::Json | Format::Text => (),
+        Format::Bytes
+        | Format::Regex(_)
+        | Format::Grok(_)
+        | Format::Json
+        | Format::Text
+        | Format::Logfmt
+        | Format::Syslog
+        | Format::Cef
+        | Format::AccessLog(_)
+        | Format::Msgpack
+        | Format::Cbor => (),
     }
     Ok(())
 }
@@ -45,6 +45,7 @@ mod http;
 mod kafka;
 mod kinesis;
 mod mysql;
+mod net;
 mod postgres;
 mod protobuf;
 mod psql;
@@ -144,6 +145,7 @@ pub struct State {
     // === Materialize state. ===
     materialized_catalog_path: Option<PathBuf>,
     materialized_addr: String,
+    materialized_host: String,
     materialized_user: String,
     pgclient: tokio_postgres::Client,
 
@@ -426,6 +428,10 @@ pub(crate) async fn build(
         "testdrive.materialized-addr".into(),
         state.materialized_addr.clone(),
     );
+    vars.insert(
+        "testdrive.materialized-host".into(),
+        state.materialized_host.clone(),
+    );
     vars.insert(
         "testdrive.materialized-user".into(),
         state.materialized_user.clone(),
@@ -521,6 +527,7 @@ pub(crate) async fn build(
                     "sql-server-execute" => {
                         Box::new(sql_server::build_execute(builtin).map_err(wrap_err)?)
                     }
+                    "tcp-send" => Box::new(net::build_tcp_send(builtin).map_err(wrap_err)?),
                     "random-sleep" => {
                         Box::new(sleep::build_random_sleep(builtin).map_err(wrap_err)?)
                     }
@@ -551,6 +558,7 @@ pub(crate) async fn build(
                         }
                         continue;
                     }
+                    "udp-send" => Box::new(net::build_udp_send(builtin).map_err(wrap_err)?),
                     "verify-timestamp-compaction" => Box::new(
                         verify_timestamp_compaction::build_verify_timestamp_compaction_action(
                             builtin,
@@ -670,7 +678,7 @@ pub async fn create_state(
         None
     };
 
-    let (materialized_addr, materialized_user, pgclient, pgconn_task) = {
+    let (materialized_addr, materialized_host, materialized_user, pgclient, pgconn_task) = {
         let materialized_url = util::postgres::config_url(&config.materialized_pgconfig)?;
         let (pgclient, pgconn) = config
             .materialized_pgconfig
@@ -689,12 +697,16 @@ pub async fn create_state(
             Err(_) => "<unknown user>".to_owned(),
         };
 
-        let materialized_addr = format!(
-            "{}:{}",
-            materialized_url.host_str().unwrap(),
-            materialized_url.port().unwrap()
-        );
-        (materialized_addr, materialized_user, pgclient, pgconn_task)
+        let materialized_host = materialized_url.host_str().unwrap().to_string();
+        let materialized_addr =
+            format!("{}:{}", materialized_host, materialized_url.port().unwrap());
+        (
+            materialized_addr,
+            materialized_host,
+            materialized_user,
+            pgclient,
+            pgconn_task,
+        )
     };
 
     let schema_registry_url = config.schema_registry_url.to_owned();
@@ -780,6 +792,7 @@ pub async fn create_state(
         // === Materialize state. ===
         materialized_catalog_path,
         materialized_addr,
+        materialized_host,
         materialized_user,
         pgclient,
 
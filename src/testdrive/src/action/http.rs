@@ -12,6 +12,7 @@ use async_trait::async_trait;
 use reqwest::Method;
 
 use crate::action::{Action, ControlFlow, State};
+use crate::format::bytes;
 use crate::parser::BuiltinCommand;
 
 use reqwest::header::CONTENT_TYPE;
@@ -20,7 +21,7 @@ pub struct RequestAction {
     url: String,
     method: Method,
     content_type: Option<String>,
-    body: String,
+    body: Vec<u8>,
 }
 
 pub fn build_request(mut cmd: BuiltinCommand) -> Result<RequestAction, anyhow::Error> {
@@ -28,7 +29,7 @@ pub fn build_request(mut cmd: BuiltinCommand) -> Result<RequestAction, anyhow::E
         url: cmd.args.string("url")?,
         method: cmd.args.parse("method")?,
         content_type: cmd.args.opt_string("content-type"),
-        body: cmd.input.join("\n"),
+        body: bytes::unescape(cmd.input.join("\n").as_bytes())?,
     })
 }
 
@@ -39,7 +40,12 @@ impl Action for RequestAction {
     }
 
     async fn redo(&self, _: &mut State) -> Result<ControlFlow, anyhow::Error> {
-        println!("$ http-request {} {}\n{}", self.method, self.url, self.body);
+        println!(
+            "$ http-request {} {}\n{}",
+            self.method,
+            self.url,
+            String::from_utf8_lossy(&self.body)
+        );
 
         let client = reqwest::Client::new();
 
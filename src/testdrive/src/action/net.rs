@@ -0,0 +1,98 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Context;
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+use crate::action::{Action, ControlFlow, State};
+use crate::format::bytes;
+use crate::parser::BuiltinCommand;
+
+pub struct TcpSendAction {
+    addr: String,
+    contents: Vec<u8>,
+}
+
+pub fn build_tcp_send(mut cmd: BuiltinCommand) -> Result<TcpSendAction, anyhow::Error> {
+    let addr = cmd.args.string("addr")?;
+    let trailing_newline = cmd.args.opt_bool("trailing-newline")?.unwrap_or(true);
+    cmd.args.done()?;
+    let mut contents = vec![];
+    for line in cmd.input {
+        contents.extend(bytes::unescape(line.as_bytes())?);
+        contents.push(b'\n');
+    }
+    if !trailing_newline {
+        contents.pop();
+    }
+    Ok(TcpSendAction { addr, contents })
+}
+
+#[async_trait]
+impl Action for TcpSendAction {
+    async fn undo(&self, _: &mut State) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    async fn redo(&self, _: &mut State) -> Result<ControlFlow, anyhow::Error> {
+        println!(
+            "Sending {} bytes over TCP to {}",
+            self.contents.len(),
+            self.addr
+        );
+        let mut stream = TcpStream::connect(&self.addr)
+            .await
+            .with_context(|| format!("connecting to {}", self.addr))?;
+        stream.write_all(&self.contents).await?;
+        stream.shutdown().await?;
+        Ok(ControlFlow::Continue)
+    }
+}
+
+pub struct UdpSendAction {
+    addr: String,
+    datagrams: Vec<Vec<u8>>,
+}
+
+pub fn build_udp_send(mut cmd: BuiltinCommand) -> Result<UdpSendAction, anyhow::Error> {
+    let addr = cmd.args.string("addr")?;
+    cmd.args.done()?;
+    let datagrams = cmd
+        .input
+        .iter()
+        .map(|line| bytes::unescape(line.as_bytes()))
+        .collect::<Result<_, _>>()?;
+    Ok(UdpSendAction { addr, datagrams })
+}
+
+#[async_trait]
+impl Action for UdpSendAction {
+    async fn undo(&self, _: &mut State) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    async fn redo(&self, _: &mut State) -> Result<ControlFlow, anyhow::Error> {
+        println!(
+            "Sending {} datagrams over UDP to {}",
+            self.datagrams.len(),
+            self.addr
+        );
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket
+            .connect(&self.addr)
+            .await
+            .with_context(|| format!("connecting to {}", self.addr))?;
+        for datagram in &self.datagrams {
+            socket.send(datagram).await?;
+        }
+        Ok(ControlFlow::Continue)
+    }
+}
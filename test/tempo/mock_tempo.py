@@ -0,0 +1,58 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""A stand-in for Tempo's search and trace by ID APIs that serves one trace."""
+
+import json
+import time
+from http.server import BaseHTTPRequestHandler, HTTPServer
+from urllib.parse import parse_qs, urlparse
+
+TRACE_ID = "5b8efff798038103d269b633813fc60c"
+
+# The trace as a protobuf message, with one `charge` span of the `checkout`
+# service.
+TRACE = bytes.fromhex(
+    "0a84010a1c0a1a0a0c736572766963652e6e616d65120a0a08636865636b6f7574"
+    "12640a0b0a0473686f701203312e3012550a105b8efff798038103d269b633813f"
+    "c60c1208eee19b7ec3c1b1742a06636861726765300239000eb63c77455c134100"
+    "3d1e9677455c134a170a10687474702e7374617475735f636f6465120318c8017a"
+    "021801"
+)
+
+
+class Handler(BaseHTTPRequestHandler):
+    # The trace starts when it is first searched for, so that it is in the
+    # source's search window however late the source is created.
+    trace_start = None
+
+    def do_GET(self) -> None:
+        url = urlparse(self.path)
+        if url.path == "/api/search":
+            traces = []
+            if parse_qs(url.query).get("tags") == ["service.name=checkout"]:
+                if Handler.trace_start is None:
+                    Handler.trace_start = time.time_ns()
+                start = str(Handler.trace_start)
+                traces.append({"traceID": TRACE_ID, "startTimeUnixNano": start})
+            self.respond("application/json", json.dumps({"traces": traces}).encode())
+        elif url.path == f"/api/traces/{TRACE_ID}":
+            self.respond("application/protobuf", TRACE)
+        else:
+            self.send_error(404)
+
+    def respond(self, content_type: str, body: bytes) -> None:
+        self.send_response(200)
+        self.send_header("Content-Type", content_type)
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+
+HTTPServer(("0.0.0.0", 3200), Handler).serve_forever()
@@ -0,0 +1,32 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+from materialize.mzcompose import Composition, Service
+from materialize.mzcompose.services import Materialized, Testdrive
+
+SERVICES = [
+    Materialized(),
+    Service(
+        name="tempo",
+        config={
+            "image": "python:3.10.2-slim",
+            "command": ["python3", "-u", "/mock_tempo.py"],
+            "ports": [3200],
+            "volumes": ["./mock_tempo.py:/mock_tempo.py"],
+        },
+    ),
+    Testdrive(),
+]
+
+
+def workflow_default(c: Composition) -> None:
+    """Test that Tempo sources ingest the traces that Tempo finds."""
+    c.start_and_wait_for_tcp(services=["materialized", "tempo"])
+    c.wait_for_materialized()
+    c.run("testdrive-svc", "*.td")